@@ -1,10 +1,11 @@
 use glui::mk_id;
 use glui::widgets::{Button, ProgressBar, TextBox, TextBoxState};
-use glui::core::{Glui};
+use glui::core::{Glui, GluiFrame, Renderer, Color, InputEvent, Layout, Size};
+use glui::backend::software::SoftwareRenderer;
+use glui::host::glutin::{App, GlutinHost};
+use glui::recording::{Recording, Replayer};
 
-mod host;
-
-use host::GlutinHost;
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
 
 struct MyState {
     counter: i32,
@@ -12,74 +13,110 @@ struct MyState {
 }
 
 fn main() {
-    let mut host = GlutinHost::new("Counter").unwrap();
-    host.add_font("default", include_bytes!("../resources/Roboto-Regular.ttf")).unwrap();
-
     let mut glui = Glui::new();
 
     let mut state = MyState {
         counter: 0,
         email: TextBoxState::new(),
     };
-    let mut running = true;
-
-    while running {
-        host.wait_events(&mut glui);
-        running = host.render(&mut glui, |frame| {
-            if ! frame.requested_close() {
-                // Exit immediately when closing was requested.
-                return false;
-            }
-
-            let label = format!("Already clicked {} times", state.counter);
-
-            let increment = Button::with_label(&label)
-                .at(10., 10.)
-                .size(150., 24.)
-                .reify(mk_id!(), frame);
-
-            if increment {
-                state.counter += 1;
-                frame.invalidate();
-            }
-
-            let decrement = Button::with_label("Decrement")
-                .at(10., 38.)
-                .size(150., 24.)
-                .reify(mk_id!(), frame);
 
-            if decrement {
-                state.counter -= 1;
-                frame.invalidate();
-            }
+    let args: Vec<String> = std::env::args().collect();
+    let (mode, path) = match args.as_slice() {
+        [_, flag, path] => (flag.as_str(), Some(path.as_str())),
+        _ => ("", None),
+    };
 
-            let reset = Button::with_label("Reset")
-                .at(10., 66.)
-                .size(150., 24.)
-                .reify(mk_id!(), frame);
+    if let ("--screenshot", Some(path)) = (mode, path) {
+        screenshot(&mut glui, &mut state, path);
+        return;
+    }
 
-            if reset {
-                state.counter = 0;
-                frame.invalidate();
+    let mut host = GlutinHost::builder()
+        .title("Counter")
+        .build()
+        .unwrap();
+    host.add_font("default", FONT_DATA).unwrap();
+
+    match (mode, path) {
+        ("--record", Some(path)) => {
+            host.start_recording();
+            host.run(&mut glui, &mut state);
+            host.stop_recording().unwrap().save(path).unwrap();
+        }
+        ("--replay", Some(path)) => {
+            let mut replayer = Replayer::new(Recording::load(path).unwrap());
+
+            while ! glui.uistate.window_close && replayer.apply_frame(&mut glui.uistate) {
+                host.poll_window_events(&mut glui);
+                host.render(&mut glui, |frame| state.update(frame));
+                host.submit_frame();
             }
+        }
+        _ => host.run(&mut glui, &mut state),
+    }
+}
 
-            let progress = state.counter as f32;
+/// Render a single frame of the UI without opening a window and save it as PNG.
+fn screenshot(glui: &mut Glui, state: &mut MyState, path: &str) {
+    let mut renderer = SoftwareRenderer::new(320, 240, 1.0);
+    renderer.add_font("default", FONT_DATA).unwrap();
+    renderer.clear(Color::from_rgb(255, 255, 255));
+    glui.uistate.apply(&InputEvent::Resized(Size { w: 320.0, h: 240.0 }));
 
-            ProgressBar::new()
-                .progress(progress)
-                .label(format!("{:.0}%", progress))
-                .at(10., 94.)
-                .size(150., 24.)
-                .reify(frame);
+    let output = glui.run_frame(&mut renderer, |frame| state.update(frame));
+    output.display_list.replay(&mut renderer);
 
-            TextBox::new(&mut state.email)
-                .hint("E-Mail address")
-                .at(10., 122.)
-                .size(150., 24.)
-                .reify(mk_id!(), frame);
+    renderer.image().save_png(path).unwrap();
+}
 
-            true
-        });
-        host.submit_frame();
+impl App for MyState {
+    fn update<R: Renderer>(&mut self, frame: &mut GluiFrame<R>) {
+        ui(self, frame);
     }
 }
+
+fn ui<R: Renderer>(state: &mut MyState, frame: &mut GluiFrame<R>) {
+    frame.layout(Layout::column().padding(10.).spacing(4.), |frame| {
+        let label = format!("Already clicked {} times", state.counter);
+
+        let increment = Button::with_label(&label)
+            .size(150., 24.)
+            .reify(mk_id!(), frame);
+
+        if increment.clicked() {
+            state.counter += 1;
+            frame.invalidate();
+        }
+
+        let decrement = Button::with_label("Decrement")
+            .size(150., 24.)
+            .reify(mk_id!(), frame);
+
+        if decrement.clicked() {
+            state.counter -= 1;
+            frame.invalidate();
+        }
+
+        let reset = Button::with_label("Reset")
+            .size(150., 24.)
+            .reify(mk_id!(), frame);
+
+        if reset.clicked() {
+            state.counter = 0;
+            frame.invalidate();
+        }
+
+        let progress = state.counter as f32;
+
+        ProgressBar::new()
+            .progress(progress)
+            .label(format!("{:.0}%", progress))
+            .size(150., 24.)
+            .reify(frame);
+
+        TextBox::new(&mut state.email)
+            .hint("E-Mail address")
+            .size(150., 24.)
+            .reify(mk_id!(), frame);
+    });
+}
@@ -0,0 +1,234 @@
+//! Conversions from glutin's window events.
+
+use crate::core::{ButtonState, InputEvent, Key, KeyEvent, Modifiers, Point, PointerButton, ScrollDelta, Size};
+use ::glutin::{ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent};
+use std::convert::TryFrom;
+
+/// The input event corresponding to the window event, if glui handles it.
+pub fn input_event(event: &WindowEvent) -> Option<InputEvent> {
+    match event {
+        WindowEvent::CursorMoved { position, .. } =>
+            Some(InputEvent::MouseMove(Point { x: position.x, y: position.y })),
+        WindowEvent::MouseInput { button, state, .. } =>
+            Some(InputEvent::PointerButton((*button).into(), (*state).into())),
+        WindowEvent::MouseWheel { delta: MouseScrollDelta::LineDelta(x, y), .. } =>
+            Some(InputEvent::Scroll(ScrollDelta::Lines(Point { x: f64::from(*x), y: f64::from(*y) }))),
+        WindowEvent::MouseWheel { delta: MouseScrollDelta::PixelDelta(delta), .. } =>
+            Some(InputEvent::Scroll(ScrollDelta::Pixels(Point { x: delta.x, y: delta.y }))),
+        WindowEvent::KeyboardInput { input, .. } =>
+            KeyEvent::try_from(*input).ok().map(InputEvent::Key),
+        WindowEvent::ReceivedCharacter(ch) =>
+            Some(InputEvent::Text(*ch)),
+        WindowEvent::CloseRequested =>
+            Some(InputEvent::CloseRequested),
+        WindowEvent::Resized(size) =>
+            Some(InputEvent::Resized(Size { w: size.width, h: size.height })),
+        _ => None,
+    }
+}
+
+impl From<ElementState> for ButtonState {
+    fn from(other: ElementState) -> ButtonState {
+        match other {
+            ElementState::Pressed => ButtonState::Pressed,
+            ElementState::Released => ButtonState::Released,
+        }
+    }
+}
+
+impl From<MouseButton> for PointerButton {
+    fn from(other: MouseButton) -> PointerButton {
+        match other {
+            MouseButton::Left => PointerButton::Left,
+            MouseButton::Right => PointerButton::Right,
+            MouseButton::Middle => PointerButton::Middle,
+            MouseButton::Other(index) => PointerButton::Other(index),
+        }
+    }
+}
+
+impl From<ModifiersState> for Modifiers {
+    fn from(other: ModifiersState) -> Modifiers {
+        Modifiers {
+            shift: other.shift,
+            ctrl: other.ctrl,
+            alt: other.alt,
+            logo: other.logo,
+        }
+    }
+}
+
+impl TryFrom<KeyboardInput> for KeyEvent {
+    type Error = ();
+
+    fn try_from(other: KeyboardInput) -> Result<KeyEvent, ()> {
+        Ok(KeyEvent {
+            key: Key::try_from(other.virtual_keycode.ok_or(())?)?,
+            modifiers: other.modifiers.into(),
+            state: other.state.into(),
+        })
+    }
+}
+
+impl TryFrom<VirtualKeyCode> for Key {
+    type Error = ();
+
+    fn try_from(other: VirtualKeyCode) -> Result<Key, ()> {
+        use VirtualKeyCode as Code;
+        let key = match other {
+            Code::Escape => Key::Escape,
+            Code::Tab => Key::Tab,
+            Code::Return | Code::NumpadEnter => Key::Enter,
+            Code::Space => Key::Space,
+            Code::Back => Key::Backspace,
+            Code::Delete => Key::Delete,
+            Code::Insert => Key::Insert,
+            Code::Home => Key::Home,
+            Code::End => Key::End,
+            Code::PageUp => Key::PageUp,
+            Code::PageDown => Key::PageDown,
+            Code::Left => Key::Left,
+            Code::Right => Key::Right,
+            Code::Up => Key::Up,
+            Code::Down => Key::Down,
+            Code::Apps => Key::Menu,
+            Code::A => Key::A,
+            Code::B => Key::B,
+            Code::C => Key::C,
+            Code::D => Key::D,
+            Code::E => Key::E,
+            Code::F => Key::F,
+            Code::G => Key::G,
+            Code::H => Key::H,
+            Code::I => Key::I,
+            Code::J => Key::J,
+            Code::K => Key::K,
+            Code::L => Key::L,
+            Code::M => Key::M,
+            Code::N => Key::N,
+            Code::O => Key::O,
+            Code::P => Key::P,
+            Code::Q => Key::Q,
+            Code::R => Key::R,
+            Code::S => Key::S,
+            Code::T => Key::T,
+            Code::U => Key::U,
+            Code::V => Key::V,
+            Code::W => Key::W,
+            Code::X => Key::X,
+            Code::Y => Key::Y,
+            Code::Z => Key::Z,
+            Code::Key0 | Code::Numpad0 => Key::Num0,
+            Code::Key1 | Code::Numpad1 => Key::Num1,
+            Code::Key2 | Code::Numpad2 => Key::Num2,
+            Code::Key3 | Code::Numpad3 => Key::Num3,
+            Code::Key4 | Code::Numpad4 => Key::Num4,
+            Code::Key5 | Code::Numpad5 => Key::Num5,
+            Code::Key6 | Code::Numpad6 => Key::Num6,
+            Code::Key7 | Code::Numpad7 => Key::Num7,
+            Code::Key8 | Code::Numpad8 => Key::Num8,
+            Code::Key9 | Code::Numpad9 => Key::Num9,
+            Code::F1 => Key::F1,
+            Code::F2 => Key::F2,
+            Code::F3 => Key::F3,
+            Code::F4 => Key::F4,
+            Code::F5 => Key::F5,
+            Code::F6 => Key::F6,
+            Code::F7 => Key::F7,
+            Code::F8 => Key::F8,
+            Code::F9 => Key::F9,
+            Code::F10 => Key::F10,
+            Code::F11 => Key::F11,
+            Code::F12 => Key::F12,
+            _ => return Err(()),
+        };
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::glutin::dpi::{LogicalPosition, LogicalSize};
+    use ::glutin::{DeviceId, TouchPhase};
+
+    fn device() -> DeviceId {
+        // Only used for constructing events, never passed to the windowing system
+        unsafe { DeviceId::dummy() }
+    }
+
+    fn keyboard_input(key: Option<VirtualKeyCode>, state: ElementState, modifiers: ModifiersState) -> KeyboardInput {
+        KeyboardInput { scancode: 0, state, virtual_keycode: key, modifiers }
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(Key::try_from(VirtualKeyCode::Return), Ok(Key::Enter));
+        assert_eq!(Key::try_from(VirtualKeyCode::NumpadEnter), Ok(Key::Enter));
+        assert_eq!(Key::try_from(VirtualKeyCode::Back), Ok(Key::Backspace));
+        assert_eq!(Key::try_from(VirtualKeyCode::Apps), Ok(Key::Menu));
+        assert_eq!(Key::try_from(VirtualKeyCode::Q), Ok(Key::Q));
+        assert_eq!(Key::try_from(VirtualKeyCode::Key7), Ok(Key::Num7));
+        assert_eq!(Key::try_from(VirtualKeyCode::Numpad7), Ok(Key::Num7));
+        assert_eq!(Key::try_from(VirtualKeyCode::F12), Ok(Key::F12));
+        assert_eq!(Key::try_from(VirtualKeyCode::LShift), Err(()));
+    }
+
+    #[test]
+    fn modifiers_and_buttons() {
+        let state = ModifiersState { shift: true, ctrl: false, alt: true, logo: false };
+        assert_eq!(Modifiers::from(state), Modifiers { shift: true, ctrl: false, alt: true, logo: false });
+        assert_eq!(Modifiers::from(ModifiersState::default()), Modifiers::NONE);
+
+        assert_eq!(PointerButton::from(MouseButton::Left), PointerButton::Left);
+        assert_eq!(PointerButton::from(MouseButton::Middle), PointerButton::Middle);
+        assert_eq!(PointerButton::from(MouseButton::Other(4)), PointerButton::Other(4));
+        assert_eq!(ButtonState::from(ElementState::Released), ButtonState::Released);
+    }
+
+    #[test]
+    fn key_events() {
+        let shift = ModifiersState { shift: true, .. ModifiersState::default() };
+        let input = keyboard_input(Some(VirtualKeyCode::Tab), ElementState::Pressed, shift);
+        assert_eq!(KeyEvent::try_from(input), Ok(KeyEvent {
+            key: Key::Tab,
+            modifiers: Modifiers::SHIFT,
+            state: ButtonState::Pressed,
+        }));
+
+        // Keys without a virtual key code or without a glui equivalent are dropped
+        let unknown = keyboard_input(None, ElementState::Pressed, shift);
+        assert_eq!(KeyEvent::try_from(unknown), Err(()));
+        let event = WindowEvent::KeyboardInput {
+            device_id: device(),
+            input: keyboard_input(Some(VirtualKeyCode::LControl), ElementState::Released, ModifiersState::default()),
+        };
+        assert_eq!(input_event(&event), None);
+    }
+
+    #[test]
+    fn window_events() {
+        let modifiers = ModifiersState::default();
+        let event = WindowEvent::CursorMoved { device_id: device(), position: LogicalPosition::new(12.5, 30.0), modifiers };
+        assert_eq!(input_event(&event), Some(InputEvent::MouseMove(Point { x: 12.5, y: 30.0 })));
+
+        let event = WindowEvent::MouseInput { device_id: device(), state: ElementState::Pressed, button: MouseButton::Right, modifiers };
+        assert_eq!(input_event(&event), Some(InputEvent::PointerButton(PointerButton::Right, ButtonState::Pressed)));
+
+        let delta = MouseScrollDelta::LineDelta(0.0, -2.0);
+        let event = WindowEvent::MouseWheel { device_id: device(), delta, phase: TouchPhase::Moved, modifiers };
+        assert_eq!(input_event(&event), Some(InputEvent::Scroll(ScrollDelta::Lines(Point { x: 0.0, y: -2.0 }))));
+        let delta = MouseScrollDelta::PixelDelta(LogicalPosition::new(3.0, 4.5));
+        let event = WindowEvent::MouseWheel { device_id: device(), delta, phase: TouchPhase::Moved, modifiers };
+        assert_eq!(input_event(&event), Some(InputEvent::Scroll(ScrollDelta::Pixels(Point { x: 3.0, y: 4.5 }))));
+
+        let key = keyboard_input(Some(VirtualKeyCode::Escape), ElementState::Pressed, modifiers);
+        let event = WindowEvent::KeyboardInput { device_id: device(), input: key };
+        assert_eq!(input_event(&event), Some(InputEvent::Key(KeyEvent { key: Key::Escape, modifiers: Modifiers::NONE, state: ButtonState::Pressed })));
+
+        assert_eq!(input_event(&WindowEvent::ReceivedCharacter('\u{8}')), Some(InputEvent::Text('\u{8}')));
+        assert_eq!(input_event(&WindowEvent::CloseRequested), Some(InputEvent::CloseRequested));
+        assert_eq!(input_event(&WindowEvent::Resized(LogicalSize::new(640.0, 480.0))), Some(InputEvent::Resized(Size { w: 640.0, h: 480.0 })));
+        assert_eq!(input_event(&WindowEvent::Focused(true)), None);
+    }
+}
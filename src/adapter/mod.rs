@@ -0,0 +1,5 @@
+//! Conversions from the events of windowing libraries to glui's `InputEvent`s,
+//! each behind a feature named after the library.
+
+#[cfg(feature = "glutin")]
+pub mod glutin;
@@ -0,0 +1,5 @@
+//! Implementations of `core::Renderer` for the supported drawing backends.
+
+#[cfg(feature = "nanovg")]
+pub mod nanovg;
+pub mod software;
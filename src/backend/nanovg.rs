@@ -0,0 +1,128 @@
+use crate::core::{Color, Font, Point, Rect, Renderer, Size};
+use nanovg::{Clip, Frame, PathOptions, Scissor, StrokeOptions, TextOptions};
+
+/// Renders glui frames using a nanovg frame.
+pub struct NanovgRenderer<'b> {
+    frame: Frame<'b>,
+    /// Stack of active clip regions, each already intersected with its predecessor.
+    clip_stack: Vec<Rect>,
+}
+
+impl<'b> NanovgRenderer<'b> {
+    pub fn new(frame: Frame<'b>) -> Self {
+        NanovgRenderer {
+            frame,
+            clip_stack: Vec::new(),
+        }
+    }
+
+    pub fn frame(&self) -> &Frame<'b> {
+        &self.frame
+    }
+
+    fn clip(&self) -> Clip {
+        match self.clip_stack.last() {
+            Some(rect) => Clip::Scissor(Scissor {
+                x: rect.x as f32,
+                y: rect.y as f32,
+                width: rect.w as f32,
+                height: rect.h as f32,
+                transform: None,
+            }),
+            None => Clip::None,
+        }
+    }
+
+    fn path_options(&self) -> PathOptions {
+        PathOptions {
+            clip: self.clip(),
+            .. PathOptions::default()
+        }
+    }
+
+    fn text_options(&self, font: Font, color: Color) -> TextOptions {
+        TextOptions {
+            size: font.size,
+            color: to_nanovg_color(color),
+            clip: self.clip(),
+            .. TextOptions::default()
+        }
+    }
+
+    fn find_font(&self, font: Font) -> Option<nanovg::Font<'b>> {
+        nanovg::Font::find(self.frame.context(), font.name).ok()
+    }
+}
+
+impl<'b> Renderer for NanovgRenderer<'b> {
+    fn fill_rect(&mut self, rect: Rect, radius: f32, color: Color) {
+        self.frame.path(|path| {
+            path.rounded_rect(rect.top_left().as_f32(), rect.size().as_f32(), radius);
+            path.fill(to_nanovg_color(color), nanovg::FillOptions::default());
+        }, self.path_options());
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, radius: f32, width: f32, color: Color) {
+        self.frame.path(|path| {
+            path.rounded_rect(rect.top_left().as_f32(), rect.size().as_f32(), radius);
+            path.stroke(to_nanovg_color(color), StrokeOptions {
+                width,
+                .. StrokeOptions::default()
+            });
+        }, self.path_options());
+    }
+
+    fn stroke_line(&mut self, from: Point, to: Point, width: f32, color: Color) {
+        self.frame.path(|path| {
+            path.move_to(from.as_f32());
+            path.line_to(to.as_f32());
+            path.stroke(to_nanovg_color(color), StrokeOptions {
+                width,
+                .. StrokeOptions::default()
+            });
+        }, self.path_options());
+    }
+
+    fn push_clip(&mut self, rect: Rect) {
+        let clip = match self.clip_stack.last() {
+            Some(current) => current.intersect(&rect),
+            None => rect,
+        };
+        self.clip_stack.push(clip);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    fn text(&mut self, text: &str, position: Point, font: Font, color: Color) {
+        if let Some(nvg_font) = self.find_font(font) {
+            self.frame.text(nvg_font, position.as_f32(), text, self.text_options(font, color));
+        }
+    }
+
+    fn text_bounds(&mut self, text: &str, font: Font) -> Size {
+        self.find_font(font).map(|nvg_font| {
+            let options = self.text_options(font, Color::new(0.0, 0.0, 0.0, 0.0));
+            let (_, bounds) = self.frame.text_bounds(nvg_font, (0.0, 0.0), text, options);
+
+            Size {
+                w: f64::from(bounds.max_x) - f64::from(bounds.min_x),
+                h: f64::from(bounds.max_y) - f64::from(bounds.min_y),
+            }
+        }).unwrap_or_else(Size::zero)
+    }
+
+    fn glyph_positions(&mut self, text: &str, position: Point, font: Font) -> Vec<f64> {
+        // nanovg computes glyph positions using the most recently selected font,
+        // hence we measure the text first in order to select the right one.
+        self.text_bounds(text, font);
+        self.frame.text_glyph_positions(position.as_f32(), text)
+            .map(|glyph| f64::from(glyph.x))
+            .collect()
+    }
+}
+
+fn to_nanovg_color(color: Color) -> nanovg::Color {
+    nanovg::Color::new(color.r, color.g, color.b, color.a)
+}
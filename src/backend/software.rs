@@ -0,0 +1,600 @@
+use crate::core::{Color, Font, Point, Rect, Renderer, Size};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// An RGBA image with 8 bits per channel and straight (non-premultiplied) alpha.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Create an image of the given size where every pixel has the given color.
+    pub fn new(width: u32, height: u32, color: Color) -> Self {
+        let pixel = to_rgba8(color);
+        let pixels = pixel.iter().cloned().cycle().take(width as usize * height as usize * 4).collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Create an image from raw RGBA bytes, which must contain exactly `width * height * 4` bytes.
+    pub fn from_rgba(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        if pixels.len() == width as usize * height as usize * 4 {
+            Some(Image { width, height, pixels })
+        } else {
+            None
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw RGBA bytes of the image in row-major order.
+    pub fn as_rgba(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let offset = self.offset(x, y);
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset .. offset + 4]);
+        pixel
+    }
+
+    pub fn set_pixel(&mut self, x: u32, y: u32, pixel: [u8; 4]) {
+        let offset = self.offset(x, y);
+        self.pixels[offset .. offset + 4].copy_from_slice(&pixel);
+    }
+
+    /// Blend the color over the pixel at the given position, scaling its alpha by `coverage`.
+    fn blend(&mut self, x: u32, y: u32, color: Color, coverage: f32) {
+        let src_alpha = color.a * coverage.min(1.0);
+        if src_alpha <= 0.0 {
+            return;
+        }
+
+        let offset = self.offset(x, y);
+        let dst = &mut self.pixels[offset .. offset + 4];
+        let dst_alpha = f32::from(dst[3]) / 255.0;
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+
+        for (channel, src) in dst.iter_mut().take(3).zip(&[color.r, color.g, color.b]) {
+            let dst_value = f32::from(*channel) / 255.0;
+            let value = (src * src_alpha + dst_value * dst_alpha * (1.0 - src_alpha)) / out_alpha;
+            *channel = to_u8(value);
+        }
+        dst[3] = to_u8(out_alpha);
+    }
+
+    /// Write the image to the given file in PNG format.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        self.write_png(io::BufWriter::new(file))
+    }
+
+    /// Encode the image as PNG into the given writer.
+    pub fn write_png<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(png_to_io_error)?;
+        writer.write_image_data(&self.pixels).map_err(png_to_io_error)
+    }
+
+    /// Read an image from a PNG file.
+    pub fn load_png<P: AsRef<Path>>(path: P) -> io::Result<Image> {
+        let file = std::fs::File::open(path)?;
+        Image::read_png(io::BufReader::new(file))
+    }
+
+    /// Decode a PNG image. Grayscale, palette and 16 bit images are converted
+    /// to 8 bit RGBA, dropping the lower 8 bits of each channel.
+    pub fn read_png<Rd: io::Read>(reader: Rd) -> io::Result<Image> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let (info, mut reader) = decoder.read_info().map_err(png_decoding_to_io_error)?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut data).map_err(png_decoding_to_io_error)?;
+
+        let pixels = match reader.output_color_type() {
+            (png::ColorType::RGBA, png::BitDepth::Eight) => data,
+            (png::ColorType::RGB, png::BitDepth::Eight) => data.chunks(3)
+                .flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+            (png::ColorType::GrayscaleAlpha, png::BitDepth::Eight) => data.chunks(2)
+                .flat_map(|ga| vec![ga[0], ga[0], ga[0], ga[1]])
+                .collect(),
+            (png::ColorType::Grayscale, png::BitDepth::Eight) => data.iter()
+                .flat_map(|gray| vec![*gray, *gray, *gray, 255])
+                .collect(),
+            (color_type, bit_depth) => return Err(io::Error::other(
+                format!("unsupported PNG format {:?} with {:?}", color_type, bit_depth)
+            )),
+        };
+
+        Image::from_rgba(info.width, info.height, pixels)
+            .ok_or_else(|| io::Error::other("PNG image data has unexpected size"))
+    }
+
+    fn offset(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "pixel ({}, {}) out of bounds", x, y);
+        (y as usize * self.width as usize + x as usize) * 4
+    }
+}
+
+/// Renders glui frames into an in-memory `Image` without requiring a GPU.
+///
+/// All primitives are anti-aliased by computing the pixel coverage of their
+/// signed distance field. Coordinates are given in logical units and scaled
+/// by the pixel ratio, just like the device pixel ratio in nanovg.
+pub struct SoftwareRenderer {
+    image: Image,
+    pixel_ratio: f32,
+    fonts: HashMap<String, rusttype::Font<'static>>,
+    /// Stack of active clip regions in physical pixels, each already intersected with its predecessor.
+    clip_stack: Vec<Rect>,
+}
+
+impl SoftwareRenderer {
+    /// Create a renderer drawing into an image of the given logical size.
+    /// The image itself is `pixel_ratio` times larger in both dimensions.
+    pub fn new(width: u32, height: u32, pixel_ratio: f32) -> Self {
+        let physical_width = (width as f32 * pixel_ratio).round() as u32;
+        let physical_height = (height as f32 * pixel_ratio).round() as u32;
+        SoftwareRenderer {
+            image: Image::new(physical_width, physical_height, Color::new(0.0, 0.0, 0.0, 0.0)),
+            pixel_ratio,
+            fonts: HashMap::new(),
+            clip_stack: Vec::new(),
+        }
+    }
+
+    /// Register a TrueType font under the given name.
+    pub fn add_font<S: Into<String>>(&mut self, name: S, data: &[u8]) -> Result<(), rusttype::Error> {
+        let font = rusttype::Font::from_bytes(data.to_vec())?;
+        self.fonts.insert(name.into(), font);
+        Ok(())
+    }
+
+    /// Fill the whole image with the given color, ignoring the current clip region.
+    pub fn clear(&mut self, color: Color) {
+        self.image = Image::new(self.image.width, self.image.height, color);
+    }
+
+    pub fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+
+    fn to_physical(&self, rect: Rect) -> Rect {
+        let ratio = f64::from(self.pixel_ratio);
+        Rect {
+            x: rect.x * ratio,
+            y: rect.y * ratio,
+            w: rect.w * ratio,
+            h: rect.h * ratio,
+        }
+    }
+
+    fn current_clip(&self) -> Rect {
+        self.clip_stack.last().cloned().unwrap_or(Rect {
+            x: 0.0,
+            y: 0.0,
+            w: f64::from(self.image.width),
+            h: f64::from(self.image.height),
+        })
+    }
+
+    /// Call `coverage` for every pixel center within the physical `bounds` and the
+    /// current clip region, and blend the color according to the returned coverage.
+    fn rasterize<F>(&mut self, bounds: Rect, color: Color, coverage: F) where
+        F: Fn(f64, f64) -> f32
+    {
+        let clip = self.current_clip();
+        let area = bounds.intersect(&clip);
+        if area.w <= 0.0 || area.h <= 0.0 {
+            return;
+        }
+
+        let min_x = area.x.floor().max(0.0) as u32;
+        let min_y = area.y.floor().max(0.0) as u32;
+        let max_x = ((area.x + area.w).ceil() as u32).min(self.image.width);
+        let max_y = ((area.y + area.h).ceil() as u32).min(self.image.height);
+
+        for y in min_y .. max_y {
+            for x in min_x .. max_x {
+                let clip_coverage = pixel_overlap(&clip, x, y);
+                let shape_coverage = coverage(f64::from(x) + 0.5, f64::from(y) + 0.5);
+                self.image.blend(x, y, color, clip_coverage * shape_coverage);
+            }
+        }
+    }
+
+    fn font_scale(&self, font: Font) -> rusttype::Scale {
+        rusttype::Scale::uniform(font.size * self.pixel_ratio)
+    }
+
+    /// Compute the horizontal offset of each glyph relative to the start of the text
+    /// in physical pixels, plus the total advance width.
+    fn layout_offsets(&self, text: &str, font: Font) -> Option<(Vec<f32>, f32)> {
+        let rt_font = self.fonts.get(font.name)?;
+        let scale = self.font_scale(font);
+
+        let mut offsets = Vec::new();
+        let mut caret = 0.0;
+        let mut last_glyph = None;
+        for ch in text.chars() {
+            let glyph = rt_font.glyph(ch).scaled(scale);
+            if let Some(last) = last_glyph {
+                caret += rt_font.pair_kerning(scale, last, glyph.id());
+            }
+            offsets.push(caret);
+            caret += glyph.h_metrics().advance_width;
+            last_glyph = Some(glyph.id());
+        }
+
+        Some((offsets, caret))
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn fill_rect(&mut self, rect: Rect, radius: f32, color: Color) {
+        let rect = self.to_physical(rect);
+        let radius = f64::from(radius * self.pixel_ratio);
+        self.rasterize(rect, color, |x, y| {
+            coverage(rounded_rect_distance(&rect, radius, x, y))
+        });
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, radius: f32, width: f32, color: Color) {
+        let rect = self.to_physical(rect);
+        let radius = f64::from(radius * self.pixel_ratio);
+        let half_width = f64::from(width * self.pixel_ratio) / 2.0;
+        let bounds = Rect {
+            x: rect.x - half_width - 1.0,
+            y: rect.y - half_width - 1.0,
+            w: rect.w + 2.0 * half_width + 2.0,
+            h: rect.h + 2.0 * half_width + 2.0,
+        };
+        self.rasterize(bounds, color, |x, y| {
+            coverage(rounded_rect_distance(&rect, radius, x, y).abs() - half_width)
+        });
+    }
+
+    fn stroke_line(&mut self, from: Point, to: Point, width: f32, color: Color) {
+        let ratio = f64::from(self.pixel_ratio);
+        let from = Point { x: from.x * ratio, y: from.y * ratio };
+        let to = Point { x: to.x * ratio, y: to.y * ratio };
+        let half_width = f64::from(width * self.pixel_ratio) / 2.0;
+
+        let bounds = Rect {
+            x: from.x.min(to.x) - half_width - 1.0,
+            y: from.y.min(to.y) - half_width - 1.0,
+            w: (from.x - to.x).abs() + 2.0 * half_width + 2.0,
+            h: (from.y - to.y).abs() + 2.0 * half_width + 2.0,
+        };
+
+        self.rasterize(bounds, color, |x, y| {
+            coverage(segment_distance(from, to, half_width, x, y))
+        });
+    }
+
+    fn push_clip(&mut self, rect: Rect) {
+        let clip = self.to_physical(rect).intersect(&self.current_clip());
+        self.clip_stack.push(clip);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    fn text(&mut self, text: &str, position: Point, font: Font, color: Color) {
+        let glyphs = match self.fonts.get(font.name) {
+            Some(rt_font) => {
+                let scale = self.font_scale(font);
+                let ascent = rt_font.v_metrics(scale).ascent;
+                let origin = rusttype::point(
+                    position.x as f32 * self.pixel_ratio,
+                    position.y as f32 * self.pixel_ratio + ascent,
+                );
+                rt_font.layout(text, scale, origin).collect::<Vec<_>>()
+            }
+            None => return,
+        };
+
+        let clip = self.current_clip();
+        for glyph in glyphs {
+            if let Some(bounds) = glyph.pixel_bounding_box() {
+                let image = &mut self.image;
+                glyph.draw(|gx, gy, glyph_coverage| {
+                    let x = bounds.min.x + gx as i32;
+                    let y = bounds.min.y + gy as i32;
+                    if x >= 0 && y >= 0 && (x as u32) < image.width && (y as u32) < image.height {
+                        let clip_coverage = pixel_overlap(&clip, x as u32, y as u32);
+                        image.blend(x as u32, y as u32, color, glyph_coverage * clip_coverage);
+                    }
+                });
+            }
+        }
+    }
+
+    fn text_bounds(&mut self, text: &str, font: Font) -> Size {
+        let advance = match self.layout_offsets(text, font) {
+            Some((_, advance)) => advance,
+            None => return Size::zero(),
+        };
+        let metrics = self.fonts[font.name].v_metrics(self.font_scale(font));
+
+        Size {
+            w: f64::from(advance / self.pixel_ratio),
+            h: f64::from((metrics.ascent - metrics.descent) / self.pixel_ratio),
+        }
+    }
+
+    fn glyph_positions(&mut self, text: &str, position: Point, font: Font) -> Vec<f64> {
+        self.layout_offsets(text, font)
+            .map(|(offsets, _)| offsets.into_iter()
+                .map(|offset| position.x + f64::from(offset / self.pixel_ratio))
+                .collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Signed distance of the point to the outline of a rounded rectangle,
+/// negative inside and positive outside.
+fn rounded_rect_distance(rect: &Rect, radius: f64, x: f64, y: f64) -> f64 {
+    let half_w = rect.w / 2.0;
+    let half_h = rect.h / 2.0;
+    let radius = radius.min(half_w).min(half_h).max(0.0);
+
+    let dx = (x - (rect.x + half_w)).abs() - (half_w - radius);
+    let dy = (y - (rect.y + half_h)).abs() - (half_h - radius);
+
+    let outside = dx.max(0.0).hypot(dy.max(0.0));
+    let inside = dx.max(dy).min(0.0);
+    outside + inside - radius
+}
+
+/// Signed distance of the point to a line segment of the given half width with butt caps.
+fn segment_distance(from: Point, to: Point, half_width: f64, x: f64, y: f64) -> f64 {
+    let dir_x = to.x - from.x;
+    let dir_y = to.y - from.y;
+    let length = dir_x.hypot(dir_y);
+    if length <= 0.0 {
+        return f64::INFINITY;
+    }
+
+    let (ux, uy) = (dir_x / length, dir_y / length);
+    let (px, py) = (x - (from.x + to.x) / 2.0, y - (from.y + to.y) / 2.0);
+
+    let along = (px * ux + py * uy).abs() - length / 2.0;
+    let across = (py * ux - px * uy).abs() - half_width;
+
+    along.max(0.0).hypot(across.max(0.0)) + along.max(across).min(0.0)
+}
+
+/// Approximate pixel coverage from the signed distance at the pixel center.
+fn coverage(distance: f64) -> f32 {
+    (0.5 - distance).clamp(0.0, 1.0) as f32
+}
+
+/// Fraction of the pixel at the given position that lies inside the rectangle.
+fn pixel_overlap(rect: &Rect, x: u32, y: u32) -> f32 {
+    let (x, y) = (f64::from(x), f64::from(y));
+    let overlap_x = ((x + 1.0).min(rect.x + rect.w) - x.max(rect.x)).clamp(0.0, 1.0);
+    let overlap_y = ((y + 1.0).min(rect.y + rect.h) - y.max(rect.y)).clamp(0.0, 1.0);
+    (overlap_x * overlap_y) as f32
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn to_rgba8(color: Color) -> [u8; 4] {
+    [to_u8(color.r), to_u8(color.g), to_u8(color.b), to_u8(color.a)]
+}
+
+fn png_decoding_to_io_error(err: png::DecodingError) -> io::Error {
+    match err {
+        png::DecodingError::IoError(err) => err,
+        other => io::Error::other(other.to_string()),
+    }
+}
+
+fn png_to_io_error(err: png::EncodingError) -> io::Error {
+    match err {
+        png::EncodingError::IoError(err) => err,
+        other => io::Error::other(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(width: u32, height: u32, color_type: png::ColorType, bit_depth: png::BitDepth, data: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(color_type);
+        encoder.set_depth(bit_depth);
+        encoder.write_header().unwrap().write_image_data(data).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let mut image = Image::new(3, 2, Color::from_rgb(10, 20, 30));
+        image.set_pixel(1, 1, [200, 100, 50, 128]);
+        let mut bytes = Vec::new();
+        image.write_png(&mut bytes).unwrap();
+        assert_eq!(Image::read_png(bytes.as_slice()).unwrap(), image);
+    }
+
+    #[test]
+    fn read_png_converts_grayscale() {
+        let bytes = encode(2, 1, png::ColorType::Grayscale, png::BitDepth::Eight, &[0, 200]);
+        let image = Image::read_png(bytes.as_slice()).unwrap();
+        assert_eq!(image.as_rgba(), &[0, 0, 0, 255, 200, 200, 200, 255]);
+
+        let bytes = encode(2, 1, png::ColorType::GrayscaleAlpha, png::BitDepth::Eight, &[0, 10, 200, 20]);
+        let image = Image::read_png(bytes.as_slice()).unwrap();
+        assert_eq!(image.as_rgba(), &[0, 0, 0, 10, 200, 200, 200, 20]);
+    }
+
+    #[test]
+    fn read_png_strips_16_bit() {
+        let data = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xff, 0xff];
+        let bytes = encode(1, 1, png::ColorType::RGBA, png::BitDepth::Sixteen, &data);
+        let image = Image::read_png(bytes.as_slice()).unwrap();
+        assert_eq!(image.as_rgba(), &[0x12, 0x56, 0x9a, 0xff]);
+    }
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const NONE: [u8; 4] = [0, 0, 0, 0];
+
+    fn renderer(pixel_ratio: f32) -> SoftwareRenderer {
+        SoftwareRenderer::new(20, 20, pixel_ratio)
+    }
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rect {
+        Rect { x, y, w, h }
+    }
+
+    fn alpha(renderer: &SoftwareRenderer, x: u32, y: u32) -> u8 {
+        renderer.image().pixel(x, y)[3]
+    }
+
+    #[test]
+    fn fill_rounded_rect() {
+        let mut renderer = renderer(1.0);
+        renderer.fill_rect(rect(2.0, 2.0, 16.0, 16.0), 6.0, Color::from_rgb(255, 0, 0));
+        let image = renderer.image();
+        assert_eq!(image.pixel(10, 10), RED);
+        assert_eq!(image.pixel(2, 10), RED);
+        assert_eq!(image.pixel(1, 10), NONE);
+        assert_eq!(image.pixel(17, 10), RED);
+        assert_eq!(image.pixel(18, 10), NONE);
+
+        // The corners are cut off, while the start of the curve is antialiased
+        assert_eq!(image.pixel(2, 2), NONE);
+        assert_eq!(image.pixel(17, 17), NONE);
+        let curve = alpha(&renderer, 3, 4);
+        assert!(curve > 0 && curve < 255, "alpha {} at the curve", curve);
+    }
+
+    #[test]
+    fn fill_covers_partial_pixels() {
+        let mut renderer = renderer(1.0);
+        renderer.fill_rect(rect(2.5, 2.0, 5.0, 4.0), 0.0, Color::from_rgb(255, 0, 0));
+        assert_eq!(alpha(&renderer, 2, 3), 128);
+        assert_eq!(alpha(&renderer, 3, 3), 255);
+        assert_eq!(alpha(&renderer, 7, 3), 128);
+        assert_eq!(alpha(&renderer, 8, 3), 0);
+    }
+
+    #[test]
+    fn fill_scales_with_pixel_ratio() {
+        let mut renderer = renderer(2.0);
+        assert_eq!(renderer.image().width(), 40);
+        renderer.fill_rect(rect(1.0, 1.0, 2.0, 2.0), 0.0, Color::from_rgb(255, 0, 0));
+        let image = renderer.image();
+        assert_eq!(image.pixel(1, 3), NONE);
+        assert_eq!(image.pixel(2, 2), RED);
+        assert_eq!(image.pixel(5, 5), RED);
+        assert_eq!(image.pixel(6, 5), NONE);
+    }
+
+    #[test]
+    fn stroke_rect_draws_outline() {
+        let mut renderer = renderer(1.0);
+        renderer.stroke_rect(rect(4.0, 4.0, 12.0, 12.0), 0.0, 2.0, Color::from_rgb(255, 0, 0));
+        let image = renderer.image();
+
+        // The stroke is centered on the outline
+        assert_eq!(image.pixel(2, 10), NONE);
+        assert_eq!(image.pixel(3, 10), RED);
+        assert_eq!(image.pixel(4, 10), RED);
+        assert_eq!(image.pixel(5, 10), NONE);
+        assert_eq!(image.pixel(10, 10), NONE);
+        assert_eq!(image.pixel(10, 15), RED);
+        assert_eq!(image.pixel(15, 15), RED);
+    }
+
+    #[test]
+    fn stroke_line_with_butt_caps() {
+        let mut renderer = renderer(1.0);
+        renderer.stroke_line(Point { x: 2.0, y: 10.0 }, Point { x: 18.0, y: 10.0 }, 2.0, Color::from_rgb(255, 0, 0));
+        let image = renderer.image();
+        assert_eq!(image.pixel(2, 9), RED);
+        assert_eq!(image.pixel(17, 10), RED);
+        assert_eq!(image.pixel(1, 10), NONE);
+        assert_eq!(image.pixel(18, 10), NONE);
+        assert_eq!(image.pixel(10, 8), NONE);
+        assert_eq!(image.pixel(10, 11), NONE);
+    }
+
+    #[test]
+    fn clip_restricts_drawing() {
+        let mut renderer = renderer(1.0);
+        renderer.push_clip(rect(0.0, 0.0, 10.5, 20.0));
+        renderer.push_clip(rect(5.0, 0.0, 20.0, 20.0));
+        renderer.fill_rect(rect(0.0, 0.0, 20.0, 20.0), 0.0, Color::from_rgb(255, 0, 0));
+        renderer.pop_clip();
+        assert_eq!(alpha(&renderer, 4, 5), 0);
+        assert_eq!(alpha(&renderer, 5, 5), 255);
+        assert_eq!(alpha(&renderer, 10, 5), 128);
+        assert_eq!(alpha(&renderer, 11, 5), 0);
+
+        // After popping, the outer clip applies again
+        renderer.fill_rect(rect(0.0, 0.0, 20.0, 20.0), 0.0, Color::from_rgb(0, 0, 255));
+        renderer.pop_clip();
+        assert_eq!(renderer.image().pixel(4, 5), [0, 0, 255, 255]);
+        assert_eq!(alpha(&renderer, 11, 5), 0);
+    }
+
+    #[test]
+    fn text_coverage_within_bounds() {
+        let mut renderer = renderer(1.0);
+        renderer.add_font("default", include_bytes!("../../resources/Roboto-Regular.ttf")).unwrap();
+        let font = Font { name: "default", size: 16.0 };
+        let bounds = renderer.text_bounds("Hi", font);
+        assert!(bounds.w > 5.0 && bounds.w < 20.0);
+
+        renderer.push_clip(rect(0.0, 0.0, 20.0, 10.0));
+        renderer.text("Hi", Point { x: 2.0, y: 0.0 }, font, Color::from_rgb(255, 0, 0));
+        renderer.pop_clip();
+        let covered: Vec<(u32, u32)> = (0 .. 20)
+            .flat_map(|y| (0 .. 20).map(move |x| (x, y)))
+            .filter(|&(x, y)| alpha(&renderer, x, y) > 0)
+            .collect();
+
+        // Only the clipped top part of the glyphs is drawn, starting at the position
+        assert!(covered.len() > 10);
+        assert!(covered.iter().all(|&(x, y)| x >= 2 && f64::from(x) < 2.0 + bounds.w + 1.0 && y < 10));
+        assert!(covered.iter().any(|&(x, y)| alpha(&renderer, x, y) > 200));
+        assert!(covered.iter().any(|&(x, y)| alpha(&renderer, x, y) < 100));
+
+        // Text in an unknown font is skipped
+        let mut renderer = self::renderer(1.0);
+        renderer.text("Hi", Point::zero(), Font { name: "missing", size: 16.0 }, Color::from_rgb(255, 0, 0));
+        assert!(renderer.image().as_rgba().iter().all(|value| *value == 0));
+    }
+}
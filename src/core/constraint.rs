@@ -0,0 +1,693 @@
+use super::{Id, Placement, Rect, SizeHint, Sizing};
+use super::layout::contribution;
+use cassowary::{RelationalOperator, Solver, Term, Variable};
+use std::collections::HashMap;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// An edge or dimension of a rectangle in a constraint layout.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    CenterX,
+    CenterY,
+    Width,
+    Height,
+}
+
+/// The rectangle of a widget in a constraint layout, or of the layout itself.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Anchors {
+    /// The ID of the rectangle, or `None` for the constraint layout.
+    target: Option<Id>,
+}
+
+impl Anchors {
+    /// The rectangle of the constraint layout itself.
+    pub fn parent() -> Self {
+        Anchors { target: None }
+    }
+
+    /// The rectangle with the given ID, which is assigned to a widget with `GluiFrame::constrained`.
+    pub fn of(id: Id) -> Self {
+        Anchors { target: Some(id) }
+    }
+
+    pub fn edge(self, edge: Edge) -> Anchor {
+        Anchor { target: self.target, edge }
+    }
+
+    pub fn left(self) -> Anchor {
+        self.edge(Edge::Left)
+    }
+
+    pub fn right(self) -> Anchor {
+        self.edge(Edge::Right)
+    }
+
+    pub fn top(self) -> Anchor {
+        self.edge(Edge::Top)
+    }
+
+    pub fn bottom(self) -> Anchor {
+        self.edge(Edge::Bottom)
+    }
+
+    pub fn center_x(self) -> Anchor {
+        self.edge(Edge::CenterX)
+    }
+
+    pub fn center_y(self) -> Anchor {
+        self.edge(Edge::CenterY)
+    }
+
+    pub fn width(self) -> Anchor {
+        self.edge(Edge::Width)
+    }
+
+    pub fn height(self) -> Anchor {
+        self.edge(Edge::Height)
+    }
+}
+
+/// A single edge or dimension of a rectangle, which is a variable of the constraint solver.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Anchor {
+    target: Option<Id>,
+    edge: Edge,
+}
+
+impl Anchor {
+    pub fn equals<E: Into<Expression>>(self, other: E) -> Constraint {
+        Expression::from(self).equals(other)
+    }
+
+    pub fn at_most<E: Into<Expression>>(self, other: E) -> Constraint {
+        Expression::from(self).at_most(other)
+    }
+
+    pub fn at_least<E: Into<Expression>>(self, other: E) -> Constraint {
+        Expression::from(self).at_least(other)
+    }
+}
+
+/// A linear combination of anchors plus a constant.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Expression {
+    terms: Vec<(Anchor, f64)>,
+    constant: f64,
+}
+
+impl Expression {
+    /// Require both sides to be equal.
+    pub fn equals<E: Into<Expression>>(self, other: E) -> Constraint {
+        Constraint::new(self - other.into(), Relation::Equal)
+    }
+
+    /// Require the left side to be less than or equal to the right side.
+    pub fn at_most<E: Into<Expression>>(self, other: E) -> Constraint {
+        Constraint::new(self - other.into(), Relation::LessOrEqual)
+    }
+
+    /// Require the left side to be greater than or equal to the right side.
+    pub fn at_least<E: Into<Expression>>(self, other: E) -> Constraint {
+        Constraint::new(self - other.into(), Relation::GreaterOrEqual)
+    }
+}
+
+impl From<Anchor> for Expression {
+    fn from(anchor: Anchor) -> Self {
+        Expression { terms: vec![(anchor, 1.0)], constant: 0.0 }
+    }
+}
+
+impl From<f64> for Expression {
+    fn from(constant: f64) -> Self {
+        Expression { terms: Vec::new(), constant }
+    }
+}
+
+impl<E: Into<Expression>> Add<E> for Expression {
+    type Output = Expression;
+
+    fn add(mut self, other: E) -> Expression {
+        let other = other.into();
+        self.terms.extend(other.terms);
+        self.constant += other.constant;
+        self
+    }
+}
+
+impl<E: Into<Expression>> Sub<E> for Expression {
+    type Output = Expression;
+
+    fn sub(self, other: E) -> Expression {
+        let other: Expression = other.into();
+        self + -other
+    }
+}
+
+impl Mul<f64> for Expression {
+    type Output = Expression;
+
+    fn mul(self, factor: f64) -> Expression {
+        Expression {
+            terms: self.terms.into_iter().map(|(anchor, coefficient)| (anchor, coefficient * factor)).collect(),
+            constant: self.constant * factor,
+        }
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+impl<E: Into<Expression>> Add<E> for Anchor {
+    type Output = Expression;
+
+    fn add(self, other: E) -> Expression {
+        Expression::from(self) + other
+    }
+}
+
+impl<E: Into<Expression>> Sub<E> for Anchor {
+    type Output = Expression;
+
+    fn sub(self, other: E) -> Expression {
+        Expression::from(self) - other
+    }
+}
+
+impl Mul<f64> for Anchor {
+    type Output = Expression;
+
+    fn mul(self, factor: f64) -> Expression {
+        Expression::from(self) * factor
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Relation {
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+}
+
+/// How important it is to satisfy a constraint. Constraints that are not
+/// required are satisfied as well as possible, in order of their strength.
+/// Among constraints of the same strength, the ones added first take precedence.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Strength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl Strength {
+    fn value(self) -> f64 {
+        match self {
+            Strength::Required => cassowary::strength::REQUIRED,
+            Strength::Strong => cassowary::strength::STRONG,
+            Strength::Medium => cassowary::strength::MEDIUM,
+            Strength::Weak => cassowary::strength::WEAK,
+        }
+    }
+}
+
+/// A linear equation or inequality between anchors.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Constraint {
+    /// The difference of the left and the right side, which is compared to zero.
+    expression: Expression,
+    relation: Relation,
+    strength: Strength,
+}
+
+impl Constraint {
+    fn new(expression: Expression, relation: Relation) -> Self {
+        Constraint {
+            expression,
+            relation,
+            strength: Strength::Required,
+        }
+    }
+
+    pub fn strength(self, strength: Strength) -> Self {
+        Constraint { strength, .. self }
+    }
+}
+
+/// Describes a container that places widgets in rectangles determined by a set
+/// of linear constraints between their edges.
+///
+/// Rectangles whose position is not constrained are placed at the top left
+/// corner of the layout, and their size defaults to the size hint of the
+/// widget placed in them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConstraintLayout {
+    pub constraints: Vec<Constraint>,
+    /// The placement of the layout itself within its parent.
+    pub placement: Placement,
+}
+
+impl Default for ConstraintLayout {
+    fn default() -> Self {
+        ConstraintLayout::new()
+    }
+}
+
+impl ConstraintLayout {
+    pub fn new() -> Self {
+        ConstraintLayout {
+            constraints: Vec::new(),
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn constraint(mut self, constraint: Constraint) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+}
+
+/// The variables describing a rectangle, from which all edges are derived.
+#[derive(Copy, Clone)]
+struct RectVariables {
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable,
+}
+
+impl RectVariables {
+    fn new() -> Self {
+        RectVariables {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+
+    fn terms(&self, edge: Edge, coefficient: f64) -> Vec<Term> {
+        let term = |variable, factor: f64| Term { variable, coefficient: coefficient * factor };
+        match edge {
+            Edge::Left => vec![term(self.left, 1.0)],
+            Edge::Top => vec![term(self.top, 1.0)],
+            Edge::Width => vec![term(self.width, 1.0)],
+            Edge::Height => vec![term(self.height, 1.0)],
+            Edge::Right => vec![term(self.left, 1.0), term(self.width, 1.0)],
+            Edge::Bottom => vec![term(self.top, 1.0), term(self.height, 1.0)],
+            Edge::CenterX => vec![term(self.left, 1.0), term(self.width, 0.5)],
+            Edge::CenterY => vec![term(self.top, 1.0), term(self.height, 0.5)],
+        }
+    }
+}
+
+/// The strength with which the region of the layout is suggested to the solver.
+/// Edit variables cannot be required, but the region should win over any constraint.
+fn region_strength() -> f64 {
+    cassowary::strength::create(999.0, 999.0, 999.0, 1.0)
+}
+
+/// The largest relative amount by which a constraint is weighted more than the
+/// ones added after it, well below the factor of 1000 between strengths.
+const TIE_BREAK: f64 = 1e-3;
+
+/// The weight of a non-required constraint, which decreases slightly with the
+/// number of constraints added before it but never reaches the next strength.
+fn tie_broken(strength: Strength, added: usize) -> f64 {
+    strength.value() * (1.0 + TIE_BREAK / (1.0 + added as f64))
+}
+
+/// Keeps the solver of a constraint layout across frames. It is only rebuilt
+/// when the constraints or the size hints of the widgets change, otherwise
+/// just the new region of the layout is suggested to it.
+pub(crate) struct ConstraintSolver {
+    solver: Solver,
+    parent: RectVariables,
+    rects: HashMap<Id, RectVariables>,
+    /// The constraints the solver was built with.
+    constraints: Vec<Constraint>,
+    /// The size hints of the widgets measured in the previous layout pass, in
+    /// the order the widgets were placed, such that the solver is always built
+    /// the same way.
+    hints: Vec<(Id, SizeHint)>,
+    /// Whether the solver needs to be rebuilt before solving.
+    dirty: bool,
+    /// The number of constraints added since the solver was rebuilt.
+    added: usize,
+    /// The constraints that could not be added to the solver when it was last rebuilt.
+    ignored: Vec<Constraint>,
+}
+
+impl ConstraintSolver {
+    pub fn new() -> Self {
+        ConstraintSolver {
+            solver: Solver::new(),
+            parent: RectVariables::new(),
+            rects: HashMap::new(),
+            constraints: Vec::new(),
+            hints: Vec::new(),
+            dirty: true,
+            added: 0,
+            ignored: Vec::new(),
+        }
+    }
+
+    /// Store the size hints measured in the current layout pass and report
+    /// whether they differ from the ones the layout was solved with.
+    pub fn update_hints(&mut self, hints: Vec<(Id, SizeHint)>) -> bool {
+        if hints == self.hints {
+            return false;
+        }
+        self.hints = hints;
+        self.dirty = true;
+        true
+    }
+
+    /// Compute the rectangles of all widgets for the given region of the layout.
+    pub fn solve(&mut self, layout: &ConstraintLayout, region: Rect) -> HashMap<Id, Rect> {
+        if self.dirty || layout.constraints != self.constraints {
+            self.rebuild(&layout.constraints);
+        }
+
+        let parent = self.parent;
+        for (variable, value) in &[
+            (parent.left, region.x),
+            (parent.top, region.y),
+            (parent.width, region.w),
+            (parent.height, region.h),
+        ] {
+            self.solver.suggest_value(*variable, *value).expect("region variables are edit variables");
+        }
+
+        let solver = &self.solver;
+        self.rects.iter().map(|(id, rect)| {
+            (*id, Rect {
+                x: solver.get_value(rect.left),
+                y: solver.get_value(rect.top),
+                w: solver.get_value(rect.width),
+                h: solver.get_value(rect.height),
+            })
+        }).collect()
+    }
+
+    fn rebuild(&mut self, constraints: &[Constraint]) {
+        self.solver.reset();
+        self.rects.clear();
+        self.constraints = constraints.to_vec();
+        self.dirty = false;
+        self.added = 0;
+        self.ignored.clear();
+
+        let parent = self.parent;
+        for variable in &[parent.left, parent.top, parent.width, parent.height] {
+            self.solver.add_edit_variable(*variable, region_strength())
+                .expect("region variables are only added once");
+        }
+
+        // The defaults of widgets placed earlier win ties, see `add`
+        let ids = self.hints.iter()
+            .map(|(id, _)| *id)
+            .chain(constraints.iter()
+                .flat_map(|constraint| constraint.expression.terms.iter())
+                .filter_map(|(anchor, _)| anchor.target))
+            .collect::<Vec<_>>();
+        for id in ids {
+            if self.rects.contains_key(&id) {
+                continue;
+            }
+            let rect = RectVariables::new();
+            self.rects.insert(id, rect);
+
+            let hint = self.hints.iter()
+                .find(|(other, _)| *other == id)
+                .map(|(_, hint)| *hint)
+                .unwrap_or_default();
+            let defaults = [
+                (Anchors::of(id).width().at_least(0.0), Strength::Required),
+                (Anchors::of(id).height().at_least(0.0), Strength::Required),
+                (Anchors::of(id).left().equals(Anchors::parent().left()), Strength::Weak),
+                (Anchors::of(id).top().equals(Anchors::parent().top()), Strength::Weak),
+                (Anchors::of(id).width().at_least(hint.min.w), Strength::Strong),
+                (Anchors::of(id).height().at_least(hint.min.h), Strength::Strong),
+                (Anchors::of(id).width().at_most(hint.max.w), Strength::Strong),
+                (Anchors::of(id).height().at_most(hint.max.h), Strength::Strong),
+                (Anchors::of(id).width().equals(hint.preferred.w), Strength::Weak),
+                (Anchors::of(id).height().equals(hint.preferred.h), Strength::Weak),
+            ];
+            for (constraint, strength) in defaults.iter().cloned() {
+                // Unbounded maximum sizes are no constraint at all
+                if constraint.expression.constant.is_finite() {
+                    self.add(&constraint.strength(strength));
+                }
+            }
+        }
+
+        for constraint in constraints {
+            self.add(constraint);
+        }
+    }
+
+    fn add(&mut self, constraint: &Constraint) {
+        let mut terms = Vec::new();
+        for (anchor, coefficient) in &constraint.expression.terms {
+            let variables = match anchor.target {
+                Some(id) => self.rects[&id],
+                None => self.parent,
+            };
+            terms.extend(variables.terms(anchor.edge, *coefficient));
+        }
+
+        let relation = match constraint.relation {
+            Relation::LessOrEqual => RelationalOperator::LessOrEqual,
+            Relation::Equal => RelationalOperator::Equal,
+            Relation::GreaterOrEqual => RelationalOperator::GreaterOrEqual,
+        };
+        // The solver breaks ties between constraints of the same strength
+        // arbitrarily, hence later constraints are weighted slightly less
+        let strength = match constraint.strength {
+            Strength::Required => constraint.strength.value(),
+            strength => tie_broken(strength, self.added),
+        };
+        self.added += 1;
+
+        let expression = cassowary::Expression::new(terms, constraint.expression.constant);
+        let constraint_result = self.solver.add_constraint(
+            cassowary::Constraint::new(expression, relation, strength)
+        );
+
+        if constraint_result.is_err() {
+            self.ignored.push(constraint.clone());
+        }
+    }
+
+    /// The constraints that conflict with required constraints added before them.
+    pub fn ignored(&self) -> &[Constraint] {
+        &self.ignored
+    }
+}
+
+/// The state of a constraint layout while its children are being created.
+pub(crate) struct ConstraintCursor {
+    id: Id,
+    content: Rect,
+    /// The solved rectangles of all widgets.
+    rects: HashMap<Id, Rect>,
+    /// The size hints of the widgets placed so far, in placement order.
+    hints: Vec<(Id, SizeHint)>,
+    /// The rectangle for the next widget.
+    requested: Option<Id>,
+    children: usize,
+}
+
+impl ConstraintCursor {
+    pub fn new(id: Id, region: Rect, rects: HashMap<Id, Rect>) -> Self {
+        ConstraintCursor {
+            id,
+            content: region,
+            rects,
+            hints: Vec::new(),
+            requested: None,
+            children: 0,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn children(&self) -> usize {
+        self.children
+    }
+
+    /// Place the next widget in the rectangle with the given ID.
+    pub fn request(&mut self, target: Id) {
+        self.requested = Some(target);
+    }
+
+    /// The solved rectangle with the given ID.
+    pub fn rect(&self, target: Id) -> Option<Rect> {
+        self.rects.get(&target).cloned()
+    }
+
+    pub fn available(&self) -> Rect {
+        match self.requested {
+            Some(target) => self.rect(target).unwrap_or(Rect { w: 0.0, h: 0.0, .. self.content }),
+            None => self.content,
+        }
+    }
+
+    /// Hand out the rectangle that was requested for the next widget, or the
+    /// whole layout if there is none.
+    pub fn allocate(&mut self, width: Sizing, height: Sizing, hint: SizeHint) -> Rect {
+        let region = self.available();
+        self.children += 1;
+
+        match self.requested.take() {
+            Some(target) => {
+                let hint = SizeHint::from_extents(
+                    contribution(width, hint.horizontal()),
+                    contribution(height, hint.vertical()),
+                );
+                self.hints.retain(|(id, _)| *id != target);
+                self.hints.push((target, hint));
+                region
+            }
+            None => region,
+        }
+    }
+
+    /// Finish the layout and return the size hints of all constrained widgets.
+    pub fn finish(self) -> Vec<(Id, SizeHint)> {
+        self.hints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Size;
+
+    const REGION: Rect = Rect { x: 0.0, y: 0.0, w: 400.0, h: 100.0 };
+
+    fn hint(w: f64, h: f64) -> SizeHint {
+        SizeHint::at_least(Size { w, h })
+    }
+
+    #[test]
+    fn text_box_next_to_button() {
+        let button = Id::from_key("button");
+        let text_box = Id::from_key("text box");
+        let layout = ConstraintLayout::new()
+            .constraint(Anchors::of(button).left().equals(Anchors::parent().left() + 10.0))
+            .constraint(Anchors::of(text_box).left().equals(Anchors::of(button).right() + 8.0))
+            .constraint(Anchors::of(text_box).right().equals(Anchors::parent().right() - 10.0));
+
+        let mut solver = ConstraintSolver::new();
+        solver.update_hints(vec![(button, hint(80.0, 24.0)), (text_box, SizeHint::flexible(Size { w: 150.0, h: 24.0 }))]);
+        let rects = solver.solve(&layout, REGION);
+        assert_eq!(rects[&button], Rect { x: 10.0, y: 0.0, w: 80.0, h: 24.0 });
+        assert_eq!(rects[&text_box], Rect { x: 98.0, y: 0.0, w: 292.0, h: 24.0 });
+
+        // Only the region changes, the solver is not rebuilt
+        let rects = solver.solve(&layout, Rect { w: 300.0, .. REGION });
+        assert_eq!(rects[&text_box], Rect { x: 98.0, y: 0.0, w: 192.0, h: 24.0 });
+    }
+
+    #[test]
+    fn stronger_constraint_wins() {
+        let id = Id::from_key("widget");
+        let layout = ConstraintLayout::new()
+            .constraint(Anchors::of(id).width().equals(50.0).strength(Strength::Weak))
+            .constraint(Anchors::of(id).width().equals(100.0).strength(Strength::Strong))
+            .constraint(Anchors::of(id).height().equals(30.0).strength(Strength::Medium))
+            .constraint(Anchors::of(id).height().equals(40.0).strength(Strength::Medium));
+
+        let rects = ConstraintSolver::new().solve(&layout, REGION);
+        // Among constraints of the same strength, the first one wins
+        assert_eq!(rects[&id], Rect { x: 0.0, y: 0.0, w: 100.0, h: 30.0 });
+    }
+
+    #[test]
+    fn tie_break_stays_between_strengths() {
+        for added in &[0, 1, 10_000, 1_000_000] {
+            let weight = tie_broken(Strength::Weak, *added);
+            assert!(weight > Strength::Weak.value());
+            assert!(weight < Strength::Medium.value());
+            assert!(weight > tie_broken(Strength::Weak, added + 1));
+        }
+    }
+
+    #[test]
+    fn conflicting_required_constraints_are_reported_once() {
+        let id = Id::from_key("widget");
+        let conflict = Anchors::of(id).width().equals(50.0);
+        let layout = ConstraintLayout::new()
+            .constraint(Anchors::of(id).width().equals(100.0))
+            .constraint(conflict.clone());
+
+        let mut solver = ConstraintSolver::new();
+        let rects = solver.solve(&layout, REGION);
+        assert_eq!(rects[&id].w, 100.0);
+        assert_eq!(solver.ignored(), std::slice::from_ref(&conflict));
+
+        // Rebuilding the solver reports the conflict again instead of adding to it
+        assert!(solver.update_hints(vec![(id, hint(10.0, 10.0))]));
+        solver.solve(&layout, REGION);
+        solver.solve(&layout, REGION);
+        assert_eq!(solver.ignored(), &[conflict]);
+    }
+
+    #[test]
+    fn solver_is_rebuilt_when_hints_change() {
+        let id = Id::from_key("widget");
+        let layout = ConstraintLayout::new()
+            .constraint(Anchors::of(id).center_x().equals(Anchors::parent().center_x()));
+
+        let mut solver = ConstraintSolver::new();
+        assert!(solver.update_hints(vec![(id, hint(100.0, 20.0))]));
+        assert_eq!(solver.solve(&layout, REGION)[&id], Rect { x: 150.0, y: 0.0, w: 100.0, h: 20.0 });
+
+        assert!(! solver.update_hints(vec![(id, hint(100.0, 20.0))]));
+        assert!(solver.update_hints(vec![(id, hint(200.0, 20.0))]));
+        assert_eq!(solver.solve(&layout, REGION)[&id], Rect { x: 100.0, y: 0.0, w: 200.0, h: 20.0 });
+    }
+}
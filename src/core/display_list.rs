@@ -0,0 +1,125 @@
+use super::{Color, Font, Point, Rect, Renderer};
+use serde::{Deserialize, Serialize};
+
+/// A single drawing operation recorded while building a frame.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DrawCommand {
+    FillRect {
+        rect: Rect,
+        radius: f32,
+        color: Color,
+    },
+    StrokeRect {
+        rect: Rect,
+        radius: f32,
+        width: f32,
+        color: Color,
+    },
+    StrokeLine {
+        from: Point,
+        to: Point,
+        width: f32,
+        color: Color,
+    },
+    PushClip {
+        rect: Rect,
+    },
+    PopClip,
+    Text {
+        text: String,
+        position: Point,
+        font: String,
+        size: f32,
+        color: Color,
+    },
+}
+
+/// The draw commands issued by the widgets during one frame, in call order.
+///
+/// Recording the commands instead of drawing them immediately makes it possible
+/// to compare consecutive frames, to inspect what was drawn where, and to replay
+/// a frame onto any `Renderer` at a later point.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct DisplayList {
+    commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    pub fn new() -> Self {
+        DisplayList {
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    /// Move all commands of the other list to the end of this one.
+    pub fn append(&mut self, mut other: DisplayList) {
+        self.commands.append(&mut other.commands);
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Returns the index of the first command that differs between both lists,
+    /// or `None` if they are identical.
+    pub fn first_difference(&self, other: &DisplayList) -> Option<usize> {
+        let common = self.commands.iter().zip(&other.commands)
+            .position(|(a, b)| a != b);
+
+        match common {
+            Some(index) => Some(index),
+            None if self.commands.len() != other.commands.len() =>
+                Some(self.commands.len().min(other.commands.len())),
+            None => None,
+        }
+    }
+
+    /// Returns the position of the first text run with exactly the given text.
+    pub fn find_text(&self, needle: &str) -> Option<Point> {
+        self.commands.iter().filter_map(|command| match command {
+            DrawCommand::Text { text, position, .. } if text == needle => Some(*position),
+            _ => None,
+        }).next()
+    }
+
+    /// Execute all recorded commands on the given renderer.
+    pub fn replay<R: Renderer + ?Sized>(&self, renderer: &mut R) {
+        for command in &self.commands {
+            match command {
+                DrawCommand::FillRect { rect, radius, color } =>
+                    renderer.fill_rect(*rect, *radius, *color),
+                DrawCommand::StrokeRect { rect, radius, width, color } =>
+                    renderer.stroke_rect(*rect, *radius, *width, *color),
+                DrawCommand::StrokeLine { from, to, width, color } =>
+                    renderer.stroke_line(*from, *to, *width, *color),
+                DrawCommand::PushClip { rect } =>
+                    renderer.push_clip(*rect),
+                DrawCommand::PopClip =>
+                    renderer.pop_clip(),
+                DrawCommand::Text { text, position, font, size, color } =>
+                    renderer.text(text, *position, Font { name: font, size: *size }, *color),
+            }
+        }
+    }
+
+    /// Serialize the display list as JSON, e.g. for attaching it to bug reports.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<DisplayList> {
+        serde_json::from_str(json)
+    }
+}
@@ -0,0 +1,50 @@
+use super::{Id, Rect};
+#[cfg(debug_assertions)]
+use std::collections::{HashMap, HashSet};
+
+/// A widget ID that was used by more than one widget in the same frame.
+#[derive(Clone, Debug)]
+pub struct DuplicateId {
+    pub id: Id,
+    /// The regions of the clashing widgets, if they were clickable.
+    pub regions: Vec<Rect>,
+}
+
+/// Keeps track of the IDs used by interactive widgets during a single frame.
+#[cfg(debug_assertions)]
+#[derive(Default)]
+pub(crate) struct IdTracker {
+    clickable: HashMap<Id, Rect>,
+    focusable: HashSet<Id>,
+    duplicates: Vec<DuplicateId>,
+}
+
+#[cfg(debug_assertions)]
+impl IdTracker {
+    pub fn clickable(&mut self, id: Id, region: Rect) {
+        if let Some(previous) = self.clickable.insert(id, region) {
+            self.duplicates.push(DuplicateId {
+                id,
+                regions: vec![previous, region],
+            });
+        }
+    }
+
+    pub fn focusable(&mut self, id: Id) {
+        // Clickable widgets with the same ID have been reported already
+        if ! self.focusable.insert(id) && ! self.duplicates.iter().any(|dup| dup.id == id) {
+            self.duplicates.push(DuplicateId {
+                id,
+                regions: Vec::new(),
+            });
+        }
+    }
+
+    pub fn duplicates(&self) -> &[DuplicateId] {
+        &self.duplicates
+    }
+
+    pub fn into_duplicates(self) -> Vec<DuplicateId> {
+        self.duplicates
+    }
+}
@@ -1,28 +1,144 @@
-use super::{Glui, GluiState, Id, Style, Point, Size, Rect, KeyEvent, VirtualKeyCode, ModifiersState, ButtonState};
+use super::{Glui, GluiState, HitRegion, Response, Sense, Widget, Id, Style, Point, Size, Rect, Color, Renderer, DisplayList, DrawCommand, DuplicateId, Align, Layout, Grid, Constraint, ConstraintLayout, ScrollArea, Splitter, Orientation, Window, Layer, Placement, SizeHint, KeyEvent, Key, Modifiers, PointerButton, ButtonState};
+#[cfg(debug_assertions)]
+use super::duplicates::IdTracker;
+use super::layout::{self, FillMeasure, LayoutCursor, LinearCursor};
+use super::grid::{Cell, GridCursor, GridMeasure};
+use super::measure::Measured;
+use super::constraint::{ConstraintCursor, ConstraintSolver};
+use super::scroll::{self, ScrollCursor, ScrollState};
+use super::split::{SplitCursor, SplitState};
+use super::window::{WindowCursor, WindowState, RESIZE_HANDLES};
+use super::layer::LayerCursor;
+use std::hash::Hash;
 
-pub struct GluiFrame<'a, 'b> {
+/// The number of layout passes `Glui::run_frame` runs at most for a frame, until
+/// the sizes of all containers measured in a pass match the ones they were laid out with.
+pub const MAX_LAYOUT_PASSES: usize = 3;
+
+/// The mouse buttons that click widgets sensing clicks.
+const CLICK_BUTTONS: [PointerButton; 3] = [PointerButton::Left, PointerButton::Right, PointerButton::Middle];
+
+/// A frame built by `Glui::run_frame`.
+pub struct FrameResult<T> {
+    /// The value returned by the UI closure in the last layout pass.
+    pub value: T,
+    /// The draw commands of the last layout pass.
+    pub display_list: DisplayList,
+    /// Whether UI needs to be redrawn immediately after rendering the frame.
+    pub redraw: bool,
+    /// The IDs that were used by more than one widget in the last layout pass.
+    /// Duplicates are only detected in debug builds.
+    pub duplicate_ids: Vec<DuplicateId>,
+}
+
+/// Builds a single frame of the UI.
+///
+/// The renderer is only used for measuring text while the frame is built. All
+/// drawing operations are recorded in a `DisplayList` which is returned by `end`
+/// and can then be replayed onto the renderer.
+pub struct GluiFrame<'a, R: Renderer> {
     glui: &'a mut Glui,
-    frame: nanovg::Frame<'b>,
+    renderer: &'a mut R,
+    display_list: DisplayList,
+    /// The currently open ID scopes, innermost last.
+    id_stack: Vec<Id>,
+    /// The currently open layout containers, innermost last.
+    layout_stack: Vec<LayoutCursor>,
+    /// The currently open scroll areas, innermost last.
+    scroll_stack: Vec<ScrollCursor>,
+    /// The regions outside of which widgets cannot be interacted with, innermost last.
+    interaction_clip: Vec<Rect>,
+    /// The currently open windows, innermost last.
+    window_stack: Vec<WindowCursor>,
+    /// The draw commands of each window shown in this frame, and the layer it was shown in.
+    window_lists: Vec<(Id, Layer, DisplayList)>,
+    /// The layer widgets are currently created in.
+    layer: Layer,
+    /// The enclosing layers, innermost last.
+    layer_stack: Vec<LayerCursor>,
+    /// The draw commands issued in layers other than the one the frame started in.
+    layer_lists: Vec<(Layer, DisplayList)>,
+    /// The currently open modals, innermost last.
+    modal_stack: Vec<Id>,
+    /// The number of layout containers opened outside of any other container.
+    root_containers: usize,
+    #[cfg(debug_assertions)]
+    id_tracker: IdTracker,
     /// Whether UI needs to be redrawn immediately after rendering the current frame.
     redraw: bool,
+    /// Whether a container was measured with a different size than it was laid out with.
+    relayout: bool,
 }
 
-impl<'a, 'b> GluiFrame<'a, 'b> {
-    pub fn begin(glui: &'a mut Glui, frame: nanovg::Frame<'b>) -> Self {
+impl<'a, R: Renderer> GluiFrame<'a, R> {
+    /// Start a layout pass of a frame, see `Glui::run_frame`.
+    #[allow(clippy::redundant_field_names)]
+    pub(crate) fn begin(glui: &'a mut Glui, renderer: &'a mut R) -> Self {
         glui.uistate.begin();
         GluiFrame {
             glui: glui,
-            frame: frame,
+            renderer,
+            display_list: DisplayList::new(),
+            id_stack: Vec::new(),
+            layout_stack: Vec::new(),
+            scroll_stack: Vec::new(),
+            interaction_clip: Vec::new(),
+            window_stack: Vec::new(),
+            window_lists: Vec::new(),
+            layer: Layer::Normal,
+            layer_stack: Vec::new(),
+            layer_lists: Vec::new(),
+            modal_stack: Vec::new(),
+            root_containers: 0,
+            #[cfg(debug_assertions)]
+            id_tracker: IdTracker::default(),
             redraw: false,
+            relayout: false,
         }
     }
 
+    /// Finish the layout pass and return the recorded draw commands together
+    /// with the IDs that were used by more than one widget.
+    pub(crate) fn end(mut self) -> (DisplayList, Vec<DuplicateId>) {
+        debug_assert!(self.id_stack.is_empty(), "unbalanced push_id/pop_id in frame");
+        debug_assert!(self.layout_stack.is_empty(), "unbalanced begin_layout/end_layout in frame");
+        debug_assert!(self.scroll_stack.is_empty(), "unbalanced begin_scroll/end_scroll in frame");
+        debug_assert!(self.window_stack.is_empty(), "unbalanced begin_window/end_window in frame");
+        debug_assert!(self.layer_stack.is_empty(), "unbalanced begin_layer/end_layer in frame");
+        debug_assert!(self.modal_stack.is_empty(), "unbalanced begin_modal/end_modal in frame");
+
+        let shown: Vec<Id> = self.window_lists.iter().map(|(id, _, _)| *id).collect();
+        self.glui.windows.retain_shown(&shown);
+
+        // Layers are drawn from the bottom to the top. Within a layer, windows
+        // are drawn above the rest of the layer with the topmost one last.
+        let mut lists = vec![(Layer::Normal, 0, std::mem::take(&mut self.display_list))];
+        lists.extend(self.layer_lists.drain(..).map(|(layer, list)| (layer, 0, list)));
+        for (id, layer, list) in self.window_lists.drain(..) {
+            lists.push((layer, 1 + self.glui.windows.depth(id), list));
+        }
+        lists.sort_by_key(|(layer, order, _)| (*layer, *order));
+        for (_, _, list) in lists {
+            self.display_list.append(list);
+        }
+
+        #[cfg(debug_assertions)]
+        let duplicates = {
+            self.report_duplicate_ids();
+            std::mem::take(&mut self.id_tracker).into_duplicates()
+        };
+        #[cfg(not(debug_assertions))]
+        let duplicates = Vec::new();
+        (std::mem::take(&mut self.display_list), duplicates)
+    }
+
     pub fn style(&self) -> &Style {
         &self.glui.style
     }
 
-    pub fn nanovg(&self) -> &nanovg::Frame<'b> {
-        &self.frame
+    /// The draw commands recorded so far in this frame.
+    pub fn display_list(&self) -> &DisplayList {
+        &self.display_list
     }
 
     pub fn uistate(&self) -> &GluiState {
@@ -41,41 +157,987 @@ impl<'a, 'b> GluiFrame<'a, 'b> {
         self.redraw
     }
 
+    /// Whether the measure pass of this frame changed the size of any container.
+    /// In that case the frame was laid out with outdated sizes, and should be
+    /// discarded and built once more before it is displayed, see `MAX_LAYOUT_PASSES`.
+    pub fn requested_relayout(&self) -> bool {
+        self.relayout
+    }
+
     pub fn requested_close(&mut self) -> bool {
         ! self.glui.uistate.window_close
     }
 
-    pub fn font<S: AsRef<str>>(&self, name: S) -> Option<nanovg::Font<'b>> {
-        nanovg::Font::find(self.frame.context(), name).ok()
+    /// Open a new ID scope derived from the current one and the given key.
+    /// All widget IDs resolved with `scoped_id` until the matching `pop_id`
+    /// are unique to this scope, which allows creating the same widgets in
+    /// a loop or in a helper function that is called multiple times.
+    pub fn push_id<H: Hash + ?Sized>(&mut self, key: &H) {
+        let scope = self.id_stack.last()
+            .map(|parent| parent.with(key))
+            .unwrap_or_else(|| Id::invalid().with(key));
+        self.id_stack.push(scope);
+    }
+
+    /// Close the innermost ID scope.
+    pub fn pop_id(&mut self) {
+        self.id_stack.pop().expect("pop_id called without matching push_id");
+    }
+
+    /// Run the callback within a new ID scope, see `push_id`.
+    pub fn with_id<H, T, F>(&mut self, key: &H, build: F) -> T where
+        H: Hash + ?Sized,
+        F: FnOnce(&mut Self) -> T
+    {
+        self.push_id(key);
+        let result = build(self);
+        self.pop_id();
+        result
     }
 
-    /// Check whether the widget with the given ID got a click event for the given region.
-    pub fn clickable_widget(&mut self, id: Id, region: Rect) -> bool {
-        if region.contains(self.glui.uistate.mouse_pos) {
-            self.glui.uistate.hover_widget = Some(id);
+    /// Resolve the ID of a widget relative to the current ID scope.
+    /// Widgets should call this once with the ID they were given before using it.
+    pub fn scoped_id(&self, id: Id) -> Id {
+        match self.id_stack.last() {
+            Some(scope) => scope.combine(id),
+            None => id,
+        }
+    }
+
+    /// The region covering the whole window.
+    pub fn window_region(&self) -> Rect {
+        Rect::with_position_size(&Point::zero(), &self.glui.uistate.window_size)
+    }
 
-            if self.glui.uistate.active_widget.is_none() && self.glui.uistate.mouse_left.is_pressed() {
-                self.glui.uistate.active_widget = Some(id);
+    /// The region that is still available in the innermost layout container,
+    /// or the whole window outside of any container.
+    pub fn available_region(&self) -> Rect {
+        self.layout_stack.last()
+            .map(LayoutCursor::available)
+            .unwrap_or_else(|| self.window_region())
+    }
+
+    /// Determine the region of a widget with the given placement and size hint.
+    /// Widgets without an absolute position are placed by the innermost layout
+    /// container, which also measures its own size from the hints of its children.
+    pub fn place<H: Into<SizeHint>>(&mut self, placement: Placement, hint: H) -> Rect {
+        let hint = hint.into();
+        if placement.position.is_none() {
+            if let Some(cursor) = self.layout_stack.last_mut() {
+                return cursor.allocate(placement.width, placement.height, hint);
             }
         }
+        let container = self.available_region();
+        layout::resolve_absolute(&placement, container, hint)
+    }
 
-        let pressed = self.glui.uistate.hover_widget == Some(id)
-            && self.glui.uistate.active_widget == Some(id)
-            && ! self.glui.uistate.mouse_left.is_pressed();
-        
-        pressed
+    /// The ID of the next layout container, derived from its position among
+    /// the children of the enclosing container.
+    fn next_container_id(&mut self) -> Id {
+        match self.layout_stack.last() {
+            Some(parent) => parent.id().with(&parent.children()),
+            None => {
+                self.root_containers += 1;
+                self.scoped_id(Id::invalid()).with(&self.root_containers)
+            }
+        }
+    }
+
+    /// Store the size hint measured for a container, which is used to place it
+    /// in the next layout pass.
+    fn remember_measure(&mut self, id: Id, hint: SizeHint) {
+        let previous = self.glui.states.get::<Measured>(id).map(|measured| measured.0);
+        if previous != Some(hint) {
+            self.glui.states.insert(id, Measured(hint));
+            self.relayout = true;
+        }
+    }
+
+    /// Start a row, column or stack in the given region, sharing its main axis
+    /// according to the previous pass.
+    fn linear_cursor(&mut self, id: Id, layout: &Layout, region: Rect) -> LinearCursor {
+        let previous = self.glui.states.get::<FillMeasure>(id).cloned().unwrap_or_default();
+        LinearCursor::new(id, layout, region, previous)
+    }
+
+    /// Store how the children of a row or column shared its main axis, which is
+    /// used to split the remaining space between filling children in the next pass.
+    fn finish_linear(&mut self, cursor: &LinearCursor) {
+        let fill = cursor.fill_measure();
+        let previous = self.glui.states.get::<FillMeasure>(cursor.id()).cloned();
+        if previous != Some(fill) {
+            self.glui.states.insert(cursor.id(), fill);
+            self.relayout = true;
+        }
+    }
+
+    /// Open a layout container, which is placed like a widget and then hands out
+    /// regions to all widgets placed until the matching `end_layout`.
+    /// Returns the region of the container.
+    pub fn begin_layout(&mut self, layout: Layout) -> Rect {
+        let id = self.next_container_id();
+        let hint = self.glui.states.get::<Measured>(id).map(|measured| measured.0).unwrap_or_default();
+        let region = self.place(layout.placement, hint);
+        let cursor = self.linear_cursor(id, &layout, region);
+        self.layout_stack.push(LayoutCursor::Linear(cursor));
+        region
+    }
+
+    /// Open a grid container, which is placed like a widget and then places all
+    /// widgets until the matching `end_layout` in its cells. Widgets are placed
+    /// in reading order unless a cell is requested explicitly with `cell`.
+    /// Returns the region of the grid.
+    pub fn begin_grid(&mut self, id: Id, grid: &Grid) -> Rect {
+        let id = self.scoped_id(id);
+        let previous = self.glui.states.get::<GridMeasure>(id).cloned().unwrap_or_default();
+        let region = self.place(grid.placement, previous.natural_size(grid));
+        self.layout_stack.push(LayoutCursor::Grid(Box::new(GridCursor::new(id, grid, region, previous))));
+        region
+    }
+
+    /// Close the innermost layout container or grid.
+    pub fn end_layout(&mut self) {
+        let cursor = self.layout_stack.pop().expect("end_layout called without matching begin_layout");
+
+        match cursor {
+            LayoutCursor::Linear(linear) => {
+                let id = linear.id();
+                self.finish_linear(&linear);
+                self.remember_measure(id, linear.measure());
+            }
+            LayoutCursor::Grid(grid) => {
+                let id = grid.id();
+                let (measure, changed) = grid.finish();
+                if changed {
+                    // Automatically sized tracks depend on the measurements, hence
+                    // the grid needs to be laid out again with the new sizes.
+                    self.glui.states.insert(id, measure);
+                    self.relayout = true;
+                }
+            }
+            LayoutCursor::Split(_) => {},
+            LayoutCursor::Constraint(constraints) => {
+                let id = constraints.id();
+                let hints = constraints.finish();
+                if let Some(solver) = self.glui.states.get::<ConstraintSolver>(id) {
+                    if solver.update_hints(hints) {
+                        self.relayout = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run the callback within a layout container, see `begin_layout`.
+    pub fn layout<T, F>(&mut self, layout: Layout, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_layout(layout);
+        let result = build(self);
+        self.end_layout();
+        result
+    }
+
+    /// Open a constraint layout, which is placed like a widget and then places
+    /// all widgets until the matching `end_layout` in the rectangles solved from
+    /// its constraints. Use `constrained` to select the rectangle of a widget.
+    /// Returns the region of the layout.
+    pub fn begin_constraints(&mut self, id: Id, layout: &ConstraintLayout) -> Rect {
+        let id = self.scoped_id(id);
+        let region = self.place(layout.placement, SizeHint::default());
+        let rects = match self.glui.states.get::<ConstraintSolver>(id) {
+            Some(solver) => solver.solve(layout, region),
+            None => {
+                let mut solver = ConstraintSolver::new();
+                let rects = solver.solve(layout, region);
+                self.glui.states.insert(id, solver);
+                rects
+            }
+        };
+        self.layout_stack.push(LayoutCursor::Constraint(Box::new(ConstraintCursor::new(id, region, rects))));
+        region
+    }
+
+    /// Run the callback within a constraint layout, see `begin_constraints`.
+    pub fn constraints<T, F>(&mut self, id: Id, layout: &ConstraintLayout, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_constraints(id, layout);
+        let result = build(self);
+        self.end_layout();
+        result
+    }
+
+    /// Place the next widget in the rectangle with the given ID of the innermost
+    /// constraint layout, see `Anchors::of`.
+    pub fn constrained(&mut self, target: Id) {
+        if let Some(LayoutCursor::Constraint(constraints)) = self.layout_stack.last_mut() {
+            constraints.request(target);
+        }
+    }
+
+    /// The constraints of the constraint layout with the given ID that are ignored
+    /// because they conflict with required constraints given before them.
+    pub fn ignored_constraints(&self, id: Id) -> &[Constraint] {
+        self.glui.states.peek::<ConstraintSolver>(self.scoped_id(id)).map_or(&[], |solver| solver.ignored())
+    }
+
+    /// The solved rectangle with the given ID of the innermost constraint layout.
+    pub fn constrained_rect(&self, target: Id) -> Option<Rect> {
+        match self.layout_stack.last() {
+            Some(LayoutCursor::Constraint(constraints)) => constraints.rect(target),
+            _ => None,
+        }
+    }
+
+    /// Open a scroll area, which is placed like a widget and shows the part of
+    /// its content visible at the current scroll offset. All widgets until the
+    /// matching `end_scroll` are placed in the content layout.
+    /// Returns the region of the scroll area, including its scrollbars.
+    pub fn begin_scroll(&mut self, id: Id, area: &ScrollArea) -> Rect {
+        let id = self.scoped_id(id);
+        let region = self.place(area.placement, SizeHint::default());
+        let state = self.glui.states.get::<ScrollState>(id).cloned().unwrap_or_default();
+        let scroll = ScrollCursor::new(id, area, region, state, self.glui.style.scrollbar_width);
+
+        self.display_list.push(DrawCommand::PushClip { rect: scroll.viewport });
+        self.push_interaction_clip(scroll.viewport);
+
+        let content_id = self.next_container_id();
+        let content = self.linear_cursor(content_id, &area.content, scroll.content_region());
+        self.layout_stack.push(LayoutCursor::Linear(content));
+        self.scroll_stack.push(scroll);
+        region
+    }
+
+    /// Close the innermost scroll area, handle scrolling and draw its scrollbars.
+    pub fn end_scroll(&mut self) {
+        let content = match self.layout_stack.pop() {
+            Some(LayoutCursor::Linear(content)) => content,
+            _ => panic!("end_scroll called without matching begin_scroll"),
+        };
+        let mut scroll = self.scroll_stack.pop().expect("end_scroll called without matching begin_scroll");
+        let offset = scroll.state.offset;
+
+        self.interaction_clip.pop();
+        self.display_list.push(DrawCommand::PopClip);
+
+        // The content is as large as all of its children together
+        let content_id = content.id();
+        let measure = content.measure();
+        self.finish_linear(&content);
+        self.remember_measure(content_id, measure);
+        if scroll.state.content != measure.preferred {
+            scroll.state.content = measure.preferred;
+            self.relayout = true;
+        }
+        scroll.set_offset(scroll.state.offset);
+
+        // Areas are closed from the innermost to the outermost one,
+        // hence the innermost area below the cursor consumes the wheel
+        let delta = self.glui.uistate.scroll_delta;
+        if delta != Point::zero() && scroll.can_scroll() && self.is_over_surface(scroll.viewport) {
+            scroll.scroll_by(delta);
+            scroll.state.target = None;
+            self.glui.uistate.scroll_delta = Point::zero();
+        }
+
+        let dragging_horizontal = self.scrollbar(&mut scroll, false);
+        let dragging_vertical = self.scrollbar(&mut scroll, true);
+        if ! dragging_horizontal && ! dragging_vertical {
+            scroll.state.drag = None;
+        }
+
+        // The content has been placed with the previous offset already
+        if scroll.state.offset != offset {
+            self.relayout = true;
+        }
+
+        // Animations move the content a bit further in every frame, which does
+        // not require building the current frame again
+        if scroll.animate() {
+            self.invalidate();
+        }
+        self.glui.states.insert(scroll.id, scroll.state);
+    }
+
+    /// Run the callback within a scroll area, see `begin_scroll`.
+    pub fn scroll_area<T, F>(&mut self, id: Id, area: &ScrollArea, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_scroll(id, area);
+        let result = build(self);
+        self.end_scroll();
+        result
+    }
+
+    /// Handle dragging the thumb of a scrollbar and draw the scrollbar.
+    /// Returns whether the thumb is being dragged.
+    fn scrollbar(&mut self, scroll: &mut ScrollCursor, vertical: bool) -> bool {
+        let bar_width = self.glui.style.scrollbar_width;
+        let bar = |scroll: &ScrollCursor| if vertical {
+            scroll.vertical_scrollbar(bar_width)
+        } else {
+            scroll.horizontal_scrollbar(bar_width)
+        };
+        let (track, thumb) = match bar(scroll) {
+            Some(bar) => bar,
+            None => return false,
+        };
+
+        let thumb_id = scroll.id.with(if vertical { "vertical" } else { "horizontal" });
+        let response = self.interact(thumb_id, thumb, Sense::click());
+
+        let uistate = &self.glui.uistate;
+        let dragging = uistate.is_active(thumb_id);
+        let highlight = dragging || response.hovered;
+        if dragging {
+            let mouse = uistate.mouse_pos;
+            let (start_mouse, start_offset) = *scroll.state.drag.get_or_insert((mouse, scroll.state.offset));
+            let viewport = scroll.viewport;
+            let content = scroll.state.content;
+
+            let offset = if vertical {
+                let ratio = scroll::drag_ratio(track.h, thumb.h, viewport.h, content.h);
+                Point { y: start_offset.y + (mouse.y - start_mouse.y) * ratio, .. start_offset }
+            } else {
+                let ratio = scroll::drag_ratio(track.w, thumb.w, viewport.w, content.w);
+                Point { x: start_offset.x + (mouse.x - start_mouse.x) * ratio, .. start_offset }
+            };
+            scroll.set_offset(offset);
+            scroll.state.target = None;
+        }
+
+        // The thumb has moved if it was dragged
+        let (track, thumb) = bar(scroll).unwrap_or((track, thumb));
+        let style = &self.glui.style;
+        let track_color = style.scrollbar_track;
+        let thumb_color = if highlight { style.scrollbar_thumb_active } else { style.scrollbar_thumb };
+        self.fill_rect(track, 0.0, track_color);
+        self.fill_rect(thumb, (bar_width / 2.0) as f32, thumb_color);
+
+        dragging
+    }
+
+    /// Restrict interaction to the given region, within the current restriction.
+    fn push_interaction_clip(&mut self, region: Rect) {
+        let region = self.visible_region(region);
+        self.interaction_clip.push(region);
+    }
+
+    /// Prevent the mouse from reaching the widgets below the region, i.e. those
+    /// in lower layers or windows and those created before in the same layer.
+    /// Like hovering, this takes effect based on the regions of the previous frame.
+    pub fn block_input(&mut self, region: Rect) {
+        let region = self.visible_region(region);
+        self.add_hit_region(None, region);
+    }
+
+    fn add_hit_region(&mut self, widget: Option<Id>, region: Rect) {
+        let hit = HitRegion {
+            widget,
+            layer: self.layer,
+            depth: self.window_depth(),
+            region,
+        };
+        self.glui.uistate.add_hit_region(hit);
+    }
+
+    /// The position of the innermost open window from the bottom, or 0 outside of windows.
+    fn window_depth(&self) -> usize {
+        self.window_stack.last().map_or(0, |window| 1 + self.glui.windows.depth(window.id))
+    }
+
+    /// Whether the mouse is above the part of the region in which the widget
+    /// can be interacted with, and the widget was the topmost one below the
+    /// mouse in the previous frame.
+    fn is_hovered(&self, id: Id, region: Rect) -> bool {
+        let mouse = self.glui.uistate.mouse_pos;
+        self.visible_region(region).contains(mouse) && match self.glui.uistate.hit_at(mouse) {
+            Some(hit) => hit.widget == Some(id),
+            None => true,
+        }
+    }
+
+    /// Whether the mouse is above the part of the region in which widgets can be
+    /// interacted with, and not above another layer or window covering it.
+    fn is_over_surface(&self, region: Rect) -> bool {
+        let mouse = self.glui.uistate.mouse_pos;
+        self.visible_region(region).contains(mouse) && match self.glui.uistate.hit_at(mouse) {
+            Some(hit) => hit.layer == self.layer && hit.depth == self.window_depth(),
+            None => true,
+        }
+    }
+
+    /// The part of the region in which widgets can be interacted with.
+    fn visible_region(&self, region: Rect) -> Rect {
+        match self.interaction_clip.last() {
+            Some(clip) => region.intersect(clip),
+            None => region,
+        }
+    }
+
+    /// Open a splitter, which is placed like a widget and divides its region
+    /// into panes separated by draggable dividers. Each of the following widgets
+    /// or containers until the matching `end_layout` fills one pane.
+    /// Returns the region of the splitter.
+    pub fn begin_splitter(&mut self, id: Id, splitter: &Splitter) -> Rect {
+        let id = self.scoped_id(id);
+        let region = self.place(splitter.placement, SizeHint::default());
+
+        let divider = self.glui.style.splitter_width;
+        let pane_count = splitter.ratios.len();
+        let extent = (splitter.extent(region) - divider * pane_count.saturating_sub(1) as f64).max(0.0);
+        let mouse = match splitter.orientation {
+            Orientation::Horizontal => self.glui.uistate.mouse_pos.x,
+            Orientation::Vertical => self.glui.uistate.mouse_pos.y,
+        };
+
+        let mut state = self.glui.states.remove::<SplitState>(id)
+            .filter(|state| state.ratios.len() == pane_count)
+            .unwrap_or_else(|| SplitState::new(splitter));
+        let mut sizes = splitter.sizes(&state.ratios, extent);
+
+        // Dividers are handled before the panes are laid out, such that
+        // dragging them takes effect immediately
+        let (_, dividers) = splitter.rects(region, &sizes, divider);
+        let mut dragging = false;
+        for (index, rect) in dividers.into_iter().enumerate() {
+            let divider_id = id.with(&index);
+            if ! self.interact(divider_id, rect, Sense::click()).pressed {
+                continue;
+            }
+
+            dragging = true;
+            match state.drag.clone() {
+                Some((start, start_sizes)) => state.drag(splitter, index, &start_sizes, mouse - start),
+                None => {
+                    if self.glui.uistate.clicks(PointerButton::Left) >= 2 {
+                        // Double clicking a divider resets all panes
+                        state.ratios = splitter.ratios.clone();
+                    }
+                    state.drag = Some((mouse, splitter.sizes(&state.ratios, extent)));
+                }
+            }
+            sizes = splitter.sizes(&state.ratios, extent);
+        }
+        if ! dragging {
+            state.drag = None;
+        }
+
+        let (panes, dividers) = splitter.rects(region, &sizes, divider);
+        for (index, rect) in dividers.into_iter().enumerate() {
+            let divider_id = id.with(&index);
+            let uistate = &self.glui.uistate;
+            let color = if uistate.is_active(divider_id) || uistate.is_hover(divider_id) {
+                self.glui.style.splitter_active
+            } else {
+                self.glui.style.splitter_color
+            };
+            self.fill_rect(rect, 0.0, color);
+        }
+
+        self.glui.states.insert(id, state);
+        self.layout_stack.push(LayoutCursor::Split(SplitCursor::new(id, panes)));
+        region
+    }
+
+    /// Run the callback within a splitter, see `begin_splitter`.
+    pub fn splitter<T, F>(&mut self, id: Id, splitter: &Splitter, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_splitter(id, splitter);
+        let result = build(self);
+        self.end_layout();
+        result
+    }
+
+    /// The layer widgets are currently created in.
+    pub fn current_layer(&self) -> Layer {
+        self.layer
+    }
+
+    /// Create all widgets until the matching `end_layer` in the given layer,
+    /// where they are drawn above all lower layers and receive the mouse before
+    /// the widgets in those layers. Like windows, widgets in another layer are
+    /// not clipped to the scroll areas they were created in.
+    pub fn begin_layer(&mut self, layer: Layer) {
+        self.layer_stack.push(LayerCursor {
+            layer: self.layer,
+            parent_list: std::mem::take(&mut self.display_list),
+            parent_clip: std::mem::take(&mut self.interaction_clip),
+            parent_scrolls: std::mem::take(&mut self.scroll_stack),
+        });
+        self.layer = layer;
+    }
+
+    /// Return to the layer that was active before the matching `begin_layer`.
+    pub fn end_layer(&mut self) {
+        let cursor = self.layer_stack.pop().expect("end_layer called without matching begin_layer");
+        let list = std::mem::replace(&mut self.display_list, cursor.parent_list);
+        self.layer_lists.push((self.layer, list));
+        self.interaction_clip = cursor.parent_clip;
+        self.scroll_stack = cursor.parent_scrolls;
+        self.layer = cursor.layer;
+    }
+
+    /// Run the callback within the given layer, see `begin_layer`.
+    pub fn with_layer<T, F>(&mut self, layer: Layer, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_layer(layer);
+        let result = build(self);
+        self.end_layer();
+        result
+    }
+
+    /// Open a modal, which covers the whole UI with a dimmed background in the
+    /// modal layer. The background blocks the mouse from all widgets below it,
+    /// and while the modal is open the keyboard focus cannot leave the widgets
+    /// created until the matching `end_modal`, which are placed in a stack
+    /// centered in the window. A modal opened later in the frame is above the
+    /// earlier ones and traps the focus instead.
+    /// Returns whether Escape was pressed, in which case the modal should be closed.
+    pub fn begin_modal(&mut self, id: Id) -> bool {
+        let id = self.scoped_id(id);
+        let uistate = &mut self.glui.uistate;
+
+        // The focus moves into a modal once it is opened
+        if ! uistate.was_modal_shown(id) {
+            uistate.focus_widget = None;
+        }
+        let escape = if uistate.top_modal() == Some(id) {
+            let index = uistate.key_input.iter()
+                .position(|event| event.key == Key::Escape && event.state.is_pressed());
+            index.and_then(|index| uistate.key_input.remove(index)).is_some()
+        } else {
+            false
+        };
+        uistate.add_modal(id);
+
+        self.begin_layer(Layer::Modal);
+        self.modal_stack.push(id);
+        let region = self.window_region();
+        let dim = self.glui.style.modal_dim;
+        self.fill_rect(region, 0.0, dim);
+        self.block_input(region);
+
+        let content = Layout::stack().align(Align::Center);
+        let cursor = self.linear_cursor(id.with("content"), &content, region);
+        self.layout_stack.push(LayoutCursor::Linear(cursor));
+        escape
+    }
+
+    /// Close the innermost modal.
+    pub fn end_modal(&mut self) {
+        self.modal_stack.pop().expect("end_modal called without matching begin_modal");
+        match self.layout_stack.pop() {
+            Some(LayoutCursor::Linear(content)) => self.finish_linear(&content),
+            _ => panic!("end_modal called without matching begin_modal"),
+        }
+        self.end_layer();
+    }
+
+    /// Open a window, which floats above the rest of the UI at the position it
+    /// was last moved to. Unless the window is closed or collapsed, all widgets
+    /// until the matching `end_window` are placed in its content layout and
+    /// `true` is returned. The close button of the window clears `open`.
+    pub fn begin_window(&mut self, id: Id, window: &Window, open: &mut bool) -> bool {
+        let id = self.scoped_id(id);
+
+        // The window is drawn and interacted with independently of the enclosing UI
+        self.window_stack.push(WindowCursor {
+            id,
+            visible: false,
+            content: false,
+            parent_list: std::mem::take(&mut self.display_list),
+            parent_clip: std::mem::take(&mut self.interaction_clip),
+            parent_scrolls: std::mem::take(&mut self.scroll_stack),
+        });
+        if ! *open {
+            return false;
+        }
+
+        let mut state = self.glui.states.get::<WindowState>(id).cloned().unwrap_or_else(|| WindowState::new(window));
+        let uistate = &self.glui.uistate;
+        let mouse = uistate.mouse_pos;
+        if uistate.active_widget.is_none() && uistate.mouse_pressed(PointerButton::Left) && self.glui.windows.window_at(&self.glui.states, mouse, self.layer) == Some(id) {
+            self.glui.windows.raise(id);
+        }
+        self.block_input(state.outline);
+
+        let title_height = self.glui.style.window_title_height;
+        let border = self.glui.style.window_resize_border;
+        let min_size = Size { w: window.min_size.w, h: window.min_size.h.max(title_height) };
+
+        // The regions of the title bar, its buttons and the resize handles do not overlap
+        let title_bar = |rect: Rect| {
+            let inset = if window.resizable { border } else { 0.0 };
+            let inner = Rect {
+                x: rect.x + inset,
+                y: rect.y + inset,
+                w: (rect.w - 2.0 * inset).max(0.0),
+                h: title_height - inset,
+            };
+            let collapse_button = Rect { w: if window.collapsible { inner.h } else { 0.0 }, .. inner };
+            let close_width = if window.closable { inner.h } else { 0.0 };
+            let close_button = Rect { x: inner.x + inner.w - close_width, w: close_width, .. inner };
+            let title = Rect {
+                x: collapse_button.x + collapse_button.w,
+                w: (inner.w - collapse_button.w - close_button.w).max(0.0),
+                .. inner
+            };
+            (title, collapse_button, close_button)
+        };
+
+        let title_id = id.with("title");
+        let mut dragging = false;
+        if self.interact(title_id, title_bar(state.rect).0, Sense::click()).pressed {
+            let (start_mouse, start_rect) = *state.drag.get_or_insert((mouse, state.rect));
+            state.rect.x = start_rect.x + mouse.x - start_mouse.x;
+            state.rect.y = start_rect.y + mouse.y - start_mouse.y;
+            dragging = true;
+        }
+
+        if window.resizable && ! state.collapsed {
+            for (index, sides) in RESIZE_HANDLES.iter().enumerate() {
+                let handle_id = id.with(&("resize", index));
+                if self.interact(handle_id, sides.handle(state.rect, border), Sense::click()).pressed {
+                    let (start_mouse, start_rect) = *state.drag.get_or_insert((mouse, state.rect));
+                    let delta = Point { x: mouse.x - start_mouse.x, y: mouse.y - start_mouse.y };
+                    state.rect = sides.resize(start_rect, delta, min_size);
+                    dragging = true;
+                }
+            }
+        }
+        if ! dragging {
+            state.drag = None;
+        }
+
+        let (title, collapse_button, close_button) = title_bar(state.rect);
+        let collapse_id = id.with("collapse");
+        let close_id = id.with("close");
+        if window.collapsible && self.interact(collapse_id, collapse_button, Sense::click()).clicked() {
+            state.collapsed = ! state.collapsed;
+        }
+        if window.closable && self.interact(close_id, close_button, Sense::click()).clicked() {
+            *open = false;
+            self.glui.states.insert(id, state);
+            return false;
+        }
+
+        state.outline = if state.collapsed { Rect { h: title_height, .. state.rect } } else { state.rect };
+        state.layer = self.layer;
+        self.glui.states.insert(id, state);
+        self.glui.windows.show(id);
+        self.draw_window(id, window, &state, title, collapse_button, close_button);
+
+        let cursor = self.window_stack.last_mut().expect("window was opened above");
+        cursor.visible = true;
+        if state.collapsed {
+            return false;
+        }
+        cursor.content = true;
+
+        let content = Rect {
+            y: state.rect.y + title_height,
+            h: (state.rect.h - title_height).max(0.0),
+            .. state.rect
+        };
+        let clip = if window.resizable {
+            Rect {
+                x: content.x + border,
+                w: (content.w - 2.0 * border).max(0.0),
+                h: (content.h - border).max(0.0),
+                .. content
+            }
+        } else {
+            content
+        };
+        self.display_list.push(DrawCommand::PushClip { rect: content });
+        self.interaction_clip.push(clip);
+        let cursor = self.linear_cursor(id.with("content"), &window.content, content);
+        self.layout_stack.push(LayoutCursor::Linear(cursor));
+        true
+    }
+
+    /// Close the innermost window.
+    pub fn end_window(&mut self) {
+        let window = self.window_stack.pop().expect("end_window called without matching begin_window");
+        if window.content {
+            match self.layout_stack.pop() {
+                Some(LayoutCursor::Linear(content)) => self.finish_linear(&content),
+                _ => panic!("end_window called without matching begin_window"),
+            }
+            self.display_list.push(DrawCommand::PopClip);
+        }
+
+        let list = std::mem::replace(&mut self.display_list, window.parent_list);
+        self.interaction_clip = window.parent_clip;
+        self.scroll_stack = window.parent_scrolls;
+        if window.visible {
+            self.window_lists.push((window.id, self.layer, list));
+        }
+    }
+
+    /// Run the callback within a window, see `begin_window`.
+    /// Returns `None` if the window is closed or collapsed.
+    pub fn window<T, F>(&mut self, id: Id, window: &Window, open: &mut bool, build: F) -> Option<T> where
+        F: FnOnce(&mut Self) -> T
+    {
+        let result = if self.begin_window(id, window, open) {
+            Some(build(self))
+        } else {
+            None
+        };
+        self.end_window();
+        result
+    }
+
+    /// The region of the window with the given ID, as of the last time it was shown.
+    pub fn window_rect(&self, id: Id) -> Option<Rect> {
+        self.glui.states.peek::<WindowState>(self.scoped_id(id)).map(|state| state.rect)
+    }
+
+    fn draw_window(&mut self, id: Id, window: &Window, state: &WindowState, title: Rect, collapse_button: Rect, close_button: Rect) {
+        let style = &self.glui.style;
+        let radius = style.widget_border.radius;
+        let border_color = style.widget_border.color;
+        let foreground = style.foreground_color;
+        let background = style.window_background;
+        let title_color = if self.glui.windows.topmost() == Some(id) {
+            style.window_title_active
+        } else {
+            style.window_title
+        };
+        let hover_color = style.clickable_hover;
+        let padding = style.widget_padding;
+        let title_bar = Rect { h: style.window_title_height, .. state.rect };
+
+        self.fill_rect(state.outline, radius, background);
+        self.fill_rect(title_bar, radius, title_color);
+
+        if window.collapsible {
+            if self.glui.uistate.is_hover(id.with("collapse")) {
+                self.fill_rect(collapse_button, radius, hover_color);
+            }
+            // A chevron pointing right when collapsed and down otherwise
+            let icon = collapse_button.center(Size { w: 8.0, h: 8.0 });
+            let (start, tip, end) = if state.collapsed {
+                (icon.top_left(), Point { x: icon.x + icon.w, y: icon.y + icon.h / 2.0 }, Point { x: icon.x, y: icon.y + icon.h })
+            } else {
+                (icon.top_left(), Point { x: icon.x + icon.w / 2.0, y: icon.y + icon.h }, icon.top_right())
+            };
+            self.stroke_line(start, tip, 1.5, foreground);
+            self.stroke_line(tip, end, 1.5, foreground);
+        }
+        if window.closable {
+            if self.glui.uistate.is_hover(id.with("close")) {
+                self.fill_rect(close_button, radius, hover_color);
+            }
+            let icon = close_button.center(Size { w: 8.0, h: 8.0 });
+            self.stroke_line(icon.top_left(), icon.bottom_right(), 1.5, foreground);
+            self.stroke_line(icon.top_right(), Point { x: icon.x, y: icon.y + icon.h }, 1.5, foreground);
+        }
+
+        let text_region = if window.collapsible {
+            title
+        } else {
+            Rect { x: title.x + padding, w: (title.w - padding).max(0.0), .. title }
+        };
+        let text_bounds = self.text_measure(&window.title);
+        let position = Point { x: text_region.x, y: title_bar.center(text_bounds).y };
+        self.clipped(text_region, |frame| frame.text_render(&window.title, position, foreground));
+
+        self.stroke_rect(state.outline, radius, 1.0, border_color);
+    }
+
+    /// Run the callback within a grid, see `begin_grid`.
+    pub fn grid<T, F>(&mut self, id: Id, grid: &Grid, build: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.begin_grid(id, grid);
+        let result = build(self);
+        self.end_layout();
+        result
+    }
+
+    /// Place the next widget in the given cell of the innermost grid.
+    pub fn cell(&mut self, row: usize, column: usize) {
+        self.cell_span(row, column, 1, 1);
+    }
+
+    /// Place the next widget in the innermost grid, spanning multiple rows and columns.
+    pub fn cell_span(&mut self, row: usize, column: usize, row_span: usize, column_span: usize) {
+        if let Some(LayoutCursor::Grid(grid)) = self.layout_stack.last_mut() {
+            grid.request(Cell { row, column, row_span, column_span });
+        }
+    }
+
+    /// Insert empty space before the next widget in the innermost layout container.
+    pub fn add_space(&mut self, amount: f64) {
+        if let Some(cursor) = self.layout_stack.last_mut() {
+            cursor.add_space(amount);
+        }
     }
 
-    /// Handle the interactions of a focusable widget with the current focus.
-    /// The `assume_focus` parameter causes a widget to immediately assume focus
-    /// which could be the case after e.g. being clicked.
-    /// The callback is called for each key event and should return false for
-    /// preventing the default behavior of such an event (e.g. a focus switch).
-    pub fn focusable_widget<F>(&mut self, id: Id, assume_focus: bool, mut handle_key: F) -> bool where
+    /// The IDs that were used by more than one widget so far in this frame.
+    /// Duplicates are only detected in debug builds.
+    pub fn duplicate_ids(&self) -> &[DuplicateId] {
+        #[cfg(debug_assertions)]
+        {
+            self.id_tracker.duplicates()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            &[]
+        }
+    }
+
+    /// Log every duplicate ID the first time it occurs and highlight
+    /// the offending widgets if requested. IDs that are no longer duplicated
+    /// are forgotten, such that they are logged again if they clash later.
+    #[cfg(debug_assertions)]
+    fn report_duplicate_ids(&mut self) {
+        let duplicates = self.id_tracker.duplicates();
+        self.glui.reported_duplicates.retain(|id| duplicates.iter().any(|duplicate| duplicate.id == *id));
+
+        for duplicate in duplicates {
+            if self.glui.reported_duplicates.insert(duplicate.id) {
+                let source = duplicate.id.source()
+                    .map(|location| location.to_string())
+                    .unwrap_or_else(|| "an unknown location".to_owned());
+                eprintln!("glui: widget ID generated at {} is used by multiple widgets", source);
+            }
+
+            if self.glui.debug.highlight_duplicate_ids {
+                for region in &duplicate.regions {
+                    self.display_list.push(DrawCommand::StrokeRect {
+                        rect: *region,
+                        radius: 0.0,
+                        width: 2.0,
+                        color: Color::from_rgb(255, 0, 0),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Handle the interactions of a widget covering the given region, which
+    /// it senses according to `sense`. Only the topmost widget below the mouse
+    /// is hovered and can be clicked, see `block_input`. Widgets sensing focus
+    /// take it when clicked, and the focus is moved between them with Tab and
+    /// Shift+Tab in the order they are created.
+    pub fn interact(&mut self, id: Id, region: Rect, sense: Sense) -> Response {
+        self.interact_with_keys(id, region, sense, |_| true)
+    }
+
+    /// Like `interact`, but the callback is called for each key event received
+    /// while the widget has the focus, and should return false for preventing
+    /// the default behavior of such an event (e.g. a focus switch).
+    /// When the focus moved to the widget with the keyboard or by `request_focus`,
+    /// the enclosing scroll areas scroll its region into view.
+    pub fn interact_with_keys<F>(&mut self, id: Id, region: Rect, sense: Sense, handle_key: F) -> Response where
         F: FnMut(&KeyEvent) -> bool
     {
-        if self.glui.uistate.focus_widget.is_none() || assume_focus {
+        #[cfg(debug_assertions)]
+        self.id_tracker.clickable(id, region);
+
+        let visible = self.visible_region(region);
+        self.add_hit_region(Some(id), visible);
+
+        let hovered = self.is_hovered(id, region);
+        let uistate = &mut self.glui.uistate;
+        if hovered {
+            uistate.hover_widget = Some(id);
+
+            // Widgets become the target of a button when it is pressed above them,
+            // even if it was released again before this frame. Only the left
+            // button makes them active.
+            if sense.click {
+                for button in CLICK_BUTTONS {
+                    if uistate.mouse_pressed(button) {
+                        uistate.set_press_target(button, id);
+                    }
+                }
+            }
+        }
+
+        let mut clicks = Vec::new();
+        if sense.click && hovered {
+            for button in CLICK_BUTTONS {
+                if uistate.press_target(button) == Some(id) && uistate.mouse_released(button) {
+                    clicks.push(button);
+                }
+            }
+        }
+        let clicked = clicks.contains(&PointerButton::Left);
+        let pressed = uistate.is_active(id) && uistate.is_mouse_down(PointerButton::Left);
+
+        let had_focus = uistate.had_focus(id);
+        let has_focus = sense.focus && self.take_focus(id, region, clicked, handle_key);
+
+        let uistate = &self.glui.uistate;
+        Response {
+            rect: region,
+            hovered,
+            pressed,
+            double_clicked: clicked && uistate.clicks(PointerButton::Left) == 2,
+            drag_delta: if pressed { uistate.drag_delta() } else { Point::zero() },
+            has_focus,
+            gained_focus: has_focus && ! had_focus,
+            lost_focus: had_focus && ! uistate.has_focus(id),
+            changed: false,
+            context_menu: clicks.contains(&PointerButton::Right),
+            clicks,
+        }
+    }
+
+    /// The response of a non-interactive widget, which is hovered while the
+    /// mouse is above it and not above another layer or window. Unlike `interact`,
+    /// the widget does not hide the widgets below it from the mouse.
+    pub fn hover(&mut self, region: Rect) -> Response {
+        Response {
+            rect: region,
+            hovered: self.is_over_surface(region),
+            pressed: false,
+            clicks: Vec::new(),
+            double_clicked: false,
+            drag_delta: Point::zero(),
+            has_focus: false,
+            gained_focus: false,
+            lost_focus: false,
+            changed: false,
+            context_menu: false,
+        }
+    }
+
+    /// Handle the interactions of a focusable widget with the current focus,
+    /// and return whether it has the focus. The `assume_focus` parameter causes
+    /// the widget to immediately assume focus, e.g. after being clicked.
+    fn take_focus<F>(&mut self, id: Id, region: Rect, assume_focus: bool, mut handle_key: F) -> bool where
+        F: FnMut(&KeyEvent) -> bool
+    {
+        #[cfg(debug_assertions)]
+        self.id_tracker.focusable(id);
+
+        // While a modal is open, only the widgets within it can be focused
+        if let Some(modal) = self.glui.uistate.top_modal() {
+            if self.modal_stack.last() != Some(&modal) {
+                return false;
+            }
+        }
+
+        if assume_focus {
             self.glui.uistate.focus_widget = Some(id);
+            self.glui.uistate.reveal_focus = false;
+        } else if self.glui.uistate.focus_widget.is_none() {
+            self.glui.uistate.focus_widget = Some(id);
+        }
+
+        if self.glui.uistate.has_focus(id) && self.glui.uistate.reveal_focus {
+            self.glui.uistate.reveal_focus = false;
+            self.reveal(region);
         }
 
         if self.glui.uistate.has_focus(id) {
@@ -83,15 +1145,17 @@ impl<'a, 'b> GluiFrame<'a, 'b> {
                 if handle_key(&event) {
                     // Default behaviors for keys
 
-                    let focus_key = VirtualKeyCode::Tab;
-                    let next_mod = ModifiersState::default();
-                    let prev_mod = ModifiersState { shift: true, .. Default::default() };
+                    let focus_key = Key::Tab;
+                    let next_mod = Modifiers::NONE;
+                    let prev_mod = Modifiers::SHIFT;
 
                     if event.key == focus_key && event.state == ButtonState::Pressed {
                         if event.modifiers == next_mod {
                             self.glui.uistate.focus_widget = None;
+                            self.glui.uistate.reveal_focus = true;
                         } else if event.modifiers == prev_mod {
                             self.glui.uistate.focus_widget = self.glui.uistate.last_focusable_widget;
+                            self.glui.uistate.reveal_focus = true;
                         }
                     }
                 }
@@ -103,48 +1167,91 @@ impl<'a, 'b> GluiFrame<'a, 'b> {
         self.glui.uistate.focus_widget == Some(id)
     }
 
-    fn default_text_options(&self) -> nanovg::TextOptions {
-        nanovg::TextOptions {
-            size: self.glui.style.font_size,
-            ..Default::default()
-        }
+    /// Add a widget to the frame and return how it was interacted with.
+    pub fn add<W: Widget>(&mut self, widget: W) -> Response {
+        widget.ui(self)
     }
 
-    pub fn text_measure(&self, text: &str) -> Size {
-        let default_font = &self.glui.style.font_name;
-        self.font(default_font).map(|font| {
-            let (_, bounds) = self.frame.text_bounds(
-                font,
-                (0.0, 0.0),
-                text,
-                self.default_text_options()
-            );
+    /// The state of the given type stored for the widget with the given ID.
+    /// The ID is used as is, i.e. widgets pass the ID they resolved with `scoped_id`.
+    /// State that is not used for `Glui::state_lifetime` frames is dropped,
+    /// such that the state of widgets that are no longer shown does not pile up.
+    pub fn state<T: 'static>(&mut self, id: Id) -> Option<&mut T> {
+        self.glui.states.get(id)
+    }
 
-            Size {
-                w: bounds.max_x as f64 - bounds.min_x as f64,
-                h: bounds.max_y as f64 - bounds.min_y as f64
-            }
-        }).unwrap_or(Size::zero())
+    /// Store the state of the given type for the widget with the given ID, see `state`.
+    pub fn set_state<T: 'static>(&mut self, id: Id, state: T) {
+        self.glui.states.insert(id, state);
     }
 
-    pub fn text_render(&self, text: &str, position: Point, color: nanovg::Color) {
-        let default_font = &self.glui.style.font_name;
-        if let Some(font) = self.font(default_font) {
-            self.frame.text(
-                font,
-                (position.x as f32, position.y as f32),
-                text,
-                nanovg::TextOptions {
-                    color: color,
-                    .. self.default_text_options()
-                }
-            );
+    /// Run the callback with the state of the given type stored for the widget
+    /// with the given ID, or with the default value if there is none yet, and
+    /// store the possibly modified state afterwards. See `state`.
+    pub fn with_state<T, U, F>(&mut self, id: Id, build: F) -> U where
+        T: Default + 'static,
+        F: FnOnce(&mut Self, &mut T) -> U
+    {
+        let mut state = self.glui.states.remove(id).unwrap_or_default();
+        let result = build(self, &mut state);
+        self.glui.states.insert(id, state);
+        result
+    }
+
+    /// Move the keyboard focus to the widget with the given ID, which is
+    /// scrolled into view once it is created.
+    pub fn request_focus(&mut self, id: Id) {
+        let id = self.scoped_id(id);
+        self.glui.uistate.request_focus(id);
+    }
+
+    /// Smoothly scroll all enclosing scroll areas such that the region becomes visible.
+    pub fn reveal(&mut self, region: Rect) {
+        for scroll in self.scroll_stack.iter_mut().rev() {
+            scroll.reveal(region);
         }
     }
-}
 
-impl<'a, 'b> Drop for GluiFrame<'a, 'b> {
-    fn drop(&mut self) {
-        self.glui.uistate.end();
+    /// Draw a filled rectangle with the given corner radius.
+    pub fn fill_rect(&mut self, rect: Rect, radius: f32, color: Color) {
+        self.display_list.push(DrawCommand::FillRect { rect, radius, color });
+    }
+
+    /// Draw the outline of a rectangle with the given corner radius.
+    pub fn stroke_rect(&mut self, rect: Rect, radius: f32, width: f32, color: Color) {
+        self.display_list.push(DrawCommand::StrokeRect { rect, radius, width, color });
+    }
+
+    pub fn stroke_line(&mut self, from: Point, to: Point, width: f32, color: Color) {
+        self.display_list.push(DrawCommand::StrokeLine { from, to, width, color });
+    }
+
+    /// Run the callback with all drawing operations clipped to the given region.
+    pub fn clipped<T, F>(&mut self, region: Rect, draw: F) -> T where
+        F: FnOnce(&mut Self) -> T
+    {
+        self.display_list.push(DrawCommand::PushClip { rect: region });
+        let result = draw(self);
+        self.display_list.push(DrawCommand::PopClip);
+        result
+    }
+
+    pub fn text_measure(&mut self, text: &str) -> Size {
+        self.renderer.text_bounds(text, self.glui.style.font())
+    }
+
+    pub fn text_render(&mut self, text: &str, position: Point, color: Color) {
+        self.display_list.push(DrawCommand::Text {
+            text: text.to_owned(),
+            position,
+            font: self.glui.style.font_name.clone(),
+            size: self.glui.style.font_size,
+            color,
+        });
+    }
+
+    /// Compute the x coordinates of the glyphs in the text when rendered at the given position.
+    pub fn text_glyph_positions(&mut self, text: &str, position: Point) -> Vec<f64> {
+        self.renderer.glyph_positions(text, position, self.glui.style.font())
     }
 }
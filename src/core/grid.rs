@@ -0,0 +1,459 @@
+use super::layout::{align_in, shrink};
+use super::{Align, Id, Placement, Rect, Size, SizeHint, Sizing};
+
+/// How the size of a grid row or column is determined.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Track {
+    /// As large as the largest natural size of the widgets in it, as measured in the previous layout pass.
+    Auto,
+    /// A fixed size in logical units.
+    Fixed(f64),
+    /// A fraction of the extent of the grid.
+    Fraction(f64),
+    /// An equal share of the space that is left over by all other tracks.
+    Fill,
+}
+
+/// Describes a container that places widgets in rows and columns.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Grid {
+    pub columns: Vec<Track>,
+    /// Rows that are not listed here are `Track::Auto`.
+    pub rows: Vec<Track>,
+    pub column_spacing: f64,
+    pub row_spacing: f64,
+    pub padding: f64,
+    /// Alignment of widgets within their cells.
+    pub align: Align,
+    /// The placement of the grid itself within its parent.
+    pub placement: Placement,
+}
+
+impl Grid {
+    pub fn new(columns: Vec<Track>) -> Self {
+        Grid {
+            columns,
+            rows: Vec::new(),
+            column_spacing: 0.0,
+            row_spacing: 0.0,
+            padding: 0.0,
+            align: Align::Stretch,
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn rows(self, rows: Vec<Track>) -> Self {
+        Grid { rows, .. self }
+    }
+
+    pub fn spacing(self, column_spacing: f64, row_spacing: f64) -> Self {
+        Grid { column_spacing, row_spacing, .. self }
+    }
+
+    pub fn padding(self, padding: f64) -> Self {
+        Grid { padding, .. self }
+    }
+
+    pub fn align(self, align: Align) -> Self {
+        Grid { align, .. self }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+}
+
+/// The natural sizes of the content of each column and row of a grid.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(crate) struct GridMeasure {
+    pub columns: Vec<f64>,
+    pub rows: Vec<f64>,
+}
+
+impl GridMeasure {
+    /// The size of the grid if all tracks had their natural size.
+    pub fn natural_size(&self, grid: &Grid) -> Size {
+        let total = |sizes: &[f64], tracks: &[Track], count: usize, spacing: f64| {
+            let content: f64 = (0 .. count).map(|index| {
+                match tracks.get(index).cloned().unwrap_or(Track::Auto) {
+                    Track::Fixed(size) => size,
+                    _ => sizes.get(index).cloned().unwrap_or(0.0),
+                }
+            }).sum();
+            content + spacing * count.saturating_sub(1) as f64 + 2.0 * grid.padding
+        };
+
+        Size {
+            w: total(&self.columns, &grid.columns, grid.columns.len(), grid.column_spacing),
+            h: total(&self.rows, &grid.rows, grid.rows.len().max(self.rows.len()), grid.row_spacing),
+        }
+    }
+
+    fn record(sizes: &mut Vec<f64>, index: usize, size: f64) {
+        if sizes.len() <= index {
+            sizes.resize(index + 1, 0.0);
+        }
+        sizes[index] = sizes[index].max(size);
+    }
+}
+
+/// A cell of a grid, possibly spanning multiple rows and columns.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Cell {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+/// The state of a grid while its children are being created.
+pub(crate) struct GridCursor {
+    id: Id,
+    align: Align,
+    content: Rect,
+    column_tracks: Vec<Track>,
+    row_tracks: Vec<Track>,
+    column_spacing: f64,
+    row_spacing: f64,
+    /// Offset and size of each column.
+    columns: Vec<(f64, f64)>,
+    /// Offset and size of each row.
+    rows: Vec<(f64, f64)>,
+    /// The natural sizes measured in the previous layout pass.
+    previous: GridMeasure,
+    /// The natural sizes measured in the current layout pass.
+    measure: GridMeasure,
+    /// The cells spanning multiple columns or rows placed so far, with the
+    /// size of their content, which are measured once all other cells are known.
+    spans: Vec<(Cell, Size)>,
+    /// The number of children placed so far.
+    children: usize,
+    /// The cell used for the next widget if none was requested explicitly.
+    next: (usize, usize),
+    /// The cell explicitly requested for the next widget.
+    requested: Option<Cell>,
+}
+
+impl GridCursor {
+    pub fn new(id: Id, grid: &Grid, region: Rect, previous: GridMeasure) -> Self {
+        let content = shrink(region, grid.padding);
+        let columns = resolve_tracks(&grid.columns, &previous.columns, grid.columns.len(), content.w, grid.column_spacing);
+        let row_count = grid.rows.len().max(previous.rows.len());
+        let rows = resolve_tracks(&grid.rows, &previous.rows, row_count, content.h, grid.row_spacing);
+
+        GridCursor {
+            id,
+            align: grid.align,
+            content,
+            column_tracks: grid.columns.clone(),
+            row_tracks: grid.rows.clone(),
+            column_spacing: grid.column_spacing,
+            row_spacing: grid.row_spacing,
+            columns,
+            rows,
+            previous,
+            measure: GridMeasure::default(),
+            spans: Vec::new(),
+            children: 0,
+            next: (0, 0),
+            requested: None,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn children(&self) -> usize {
+        self.children
+    }
+
+    /// Place the next widget in the given cell.
+    pub fn request(&mut self, cell: Cell) {
+        self.requested = Some(cell);
+    }
+
+    pub fn available(&self) -> Rect {
+        let cell = self.requested.unwrap_or(Cell {
+            row: self.next.0,
+            column: self.next.1,
+            row_span: 1,
+            column_span: 1,
+        });
+        self.cell_rect(cell)
+    }
+
+    pub fn allocate(&mut self, width: Sizing, height: Sizing, hint: SizeHint) -> Rect {
+        let cell = self.requested.take().unwrap_or(Cell {
+            row: self.next.0,
+            column: self.next.1,
+            row_span: 1,
+            column_span: 1,
+        });
+
+        // Continue after the cell in reading order
+        self.next = if cell.column + cell.column_span >= self.columns.len() {
+            (cell.row + 1, 0)
+        } else {
+            (cell.row, cell.column + cell.column_span)
+        };
+
+        let size = Size { w: desired(width, hint.preferred.w), h: desired(height, hint.preferred.h) };
+        if cell.column_span <= 1 {
+            GridMeasure::record(&mut self.measure.columns, cell.column, size.w);
+        }
+        if cell.row_span <= 1 {
+            GridMeasure::record(&mut self.measure.rows, cell.row, size.h);
+        }
+        if cell.column_span > 1 || cell.row_span > 1 {
+            self.spans.push((cell, size));
+        }
+        self.children += 1;
+
+        let bounds = self.cell_rect(cell);
+        let (x, w) = align_in(width, hint.horizontal(), bounds.x, bounds.w, self.align);
+        let (y, h) = align_in(height, hint.vertical(), bounds.y, bounds.h, self.align);
+        Rect { x, y, w, h }
+    }
+
+    /// Finish the grid and return the sizes measured during this pass,
+    /// together with whether they differ from the previous pass.
+    pub fn finish(mut self) -> (GridMeasure, bool) {
+        for (cell, size) in std::mem::take(&mut self.spans) {
+            if cell.column_span > 1 {
+                let resolved = self.columns.iter().map(|(_, size)| *size).collect::<Vec<_>>();
+                spread(&mut self.measure.columns, &self.column_tracks, &resolved, cell.column, cell.column_span, size.w, self.column_spacing);
+            }
+            if cell.row_span > 1 {
+                let resolved = (0 .. cell.row + cell.row_span).map(|index| self.row(index).1).collect::<Vec<_>>();
+                spread(&mut self.measure.rows, &self.row_tracks, &resolved, cell.row, cell.row_span, size.h, self.row_spacing);
+            }
+        }
+        let changed = self.measure != self.previous;
+        (self.measure, changed)
+    }
+
+    fn cell_rect(&self, cell: Cell) -> Rect {
+        let column_count = self.columns.len();
+        if column_count == 0 {
+            return Rect { w: 0.0, h: 0.0, .. self.content };
+        }
+        let first_column = cell.column.min(column_count - 1);
+        let last_column = (cell.column + cell.column_span.max(1) - 1).min(column_count - 1);
+
+        let (x, _) = self.columns[first_column];
+        let (last_x, last_w) = self.columns[last_column];
+        let (y, _) = self.row(cell.row);
+        let (last_y, last_h) = self.row(cell.row + cell.row_span.max(1) - 1);
+
+        Rect {
+            x: self.content.x + x,
+            y: self.content.y + y,
+            w: last_x + last_w - x,
+            h: last_y + last_h - y,
+        }
+    }
+
+    /// Offset and size of a row, including rows that were neither declared
+    /// nor used in the previous frame.
+    fn row(&self, index: usize) -> (f64, f64) {
+        if let Some(row) = self.rows.get(index) {
+            return *row;
+        }
+
+        let mut row = self.rows.last().cloned();
+        for current in self.rows.len() ..= index {
+            let size = match self.row_tracks.get(current).cloned().unwrap_or(Track::Auto) {
+                Track::Fixed(size) => size,
+                Track::Fraction(fraction) => fraction * self.content.h,
+                Track::Auto | Track::Fill => 0.0,
+            };
+            let offset = row
+                .map(|(offset, size)| offset + size + self.row_spacing)
+                .unwrap_or(0.0);
+            row = Some((offset, size));
+        }
+        row.unwrap_or((0.0, 0.0))
+    }
+}
+
+/// The size a widget contributes to an automatically sized track.
+fn desired(sizing: Sizing, preferred: f64) -> f64 {
+    match sizing {
+        Sizing::Fixed(size) => size,
+        _ => preferred,
+    }
+}
+
+/// Grow the automatically sized tracks covered by a spanning cell evenly, such
+/// that they fit its content together with the other tracks and the spacing
+/// between them. Tracks with another sizing keep their resolved size.
+fn spread(natural: &mut Vec<f64>, tracks: &[Track], resolved: &[f64], first: usize, span: usize, size: f64, spacing: f64) {
+    let covered = first .. (first + span).min(resolved.len());
+    let is_auto = |index: &usize| tracks.get(*index).cloned().unwrap_or(Track::Auto) == Track::Auto;
+
+    let current: f64 = covered.clone().map(|index| {
+        if is_auto(&index) {
+            natural.get(index).cloned().unwrap_or(0.0)
+        } else {
+            resolved[index]
+        }
+    }).sum();
+    let missing = size - current - spacing * covered.len().saturating_sub(1) as f64;
+    let auto = covered.filter(is_auto).collect::<Vec<_>>();
+    if missing <= 0.0 || auto.is_empty() {
+        return;
+    }
+
+    for index in &auto {
+        let grown = natural.get(*index).cloned().unwrap_or(0.0) + missing / auto.len() as f64;
+        GridMeasure::record(natural, *index, grown);
+    }
+}
+
+/// Compute offset and size of `count` tracks sharing the given extent.
+fn resolve_tracks(tracks: &[Track], natural: &[f64], count: usize, extent: f64, spacing: f64) -> Vec<(f64, f64)> {
+    let track = |index: usize| tracks.get(index).cloned().unwrap_or(Track::Auto);
+
+    let mut sizes: Vec<f64> = (0 .. count).map(|index| match track(index) {
+        Track::Auto => natural.get(index).cloned().unwrap_or(0.0),
+        Track::Fixed(size) => size,
+        Track::Fraction(fraction) => fraction * extent,
+        Track::Fill => 0.0,
+    }).collect();
+
+    let fill_count = (0 .. count).filter(|index| track(*index) == Track::Fill).count();
+    if fill_count > 0 {
+        let used: f64 = sizes.iter().sum::<f64>() + spacing * count.saturating_sub(1) as f64;
+        let share = ((extent - used) / fill_count as f64).max(0.0);
+        for (index, size) in sizes.iter_mut().enumerate() {
+            if track(index) == Track::Fill {
+                *size = share;
+            }
+        }
+    }
+
+    let mut offset = 0.0;
+    sizes.into_iter().map(|size| {
+        let track = (offset, size);
+        offset += size + spacing;
+        track
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGION: Rect = Rect { x: 10.0, y: 20.0, w: 400.0, h: 300.0 };
+
+    fn hint(w: f64, h: f64) -> SizeHint {
+        SizeHint::flexible(Size { w, h })
+    }
+
+    fn span(row: usize, column: usize, row_span: usize, column_span: usize) -> Cell {
+        Cell { row, column, row_span, column_span }
+    }
+
+    #[test]
+    fn resolve_tracks_of_every_kind() {
+        let tracks = [Track::Fixed(50.0), Track::Auto, Track::Fraction(0.25), Track::Fill, Track::Fill];
+        let resolved = resolve_tracks(&tracks, &[0.0, 30.0], 5, 400.0, 10.0);
+        assert_eq!(resolved, vec![(0.0, 50.0), (60.0, 30.0), (100.0, 100.0), (210.0, 90.0), (310.0, 90.0)]);
+
+        // Fill tracks do not shrink below zero, and undeclared tracks are automatic
+        let resolved = resolve_tracks(&[Track::Fixed(500.0), Track::Fill], &[0.0, 0.0, 20.0], 3, 400.0, 0.0);
+        assert_eq!(resolved, vec![(0.0, 500.0), (500.0, 0.0), (500.0, 20.0)]);
+    }
+
+    #[test]
+    fn cells_in_reading_order() {
+        let grid = Grid::new(vec![Track::Fixed(50.0), Track::Fixed(60.0)]).rows(vec![Track::Fixed(30.0)]).spacing(10.0, 5.0);
+        let mut cursor = GridCursor::new(Id::from_key("grid"), &grid, REGION, GridMeasure::default());
+        let rects = (0 .. 3).map(|_| cursor.allocate(Sizing::Auto, Sizing::Auto, hint(0.0, 10.0))).collect::<Vec<_>>();
+        assert_eq!(rects, vec![
+            Rect { x: 10.0, y: 20.0, w: 50.0, h: 30.0 },
+            Rect { x: 70.0, y: 20.0, w: 60.0, h: 30.0 },
+            // The automatic row has not been measured yet
+            Rect { x: 10.0, y: 55.0, w: 50.0, h: 0.0 },
+        ]);
+    }
+
+    #[test]
+    fn spanning_cell_covers_tracks_and_spacing() {
+        let grid = Grid::new(vec![Track::Fixed(50.0), Track::Fixed(60.0), Track::Fixed(70.0)])
+            .rows(vec![Track::Fixed(30.0), Track::Fixed(40.0)])
+            .spacing(10.0, 5.0);
+        let mut cursor = GridCursor::new(Id::from_key("grid"), &grid, REGION, GridMeasure::default());
+        cursor.request(span(0, 1, 2, 2));
+        let rect = cursor.allocate(Sizing::Auto, Sizing::Auto, hint(0.0, 0.0));
+        assert_eq!(rect, Rect { x: 70.0, y: 20.0, w: 140.0, h: 75.0 });
+
+        // The next widget continues after the spanning cell
+        let next = cursor.allocate(Sizing::Auto, Sizing::Auto, hint(0.0, 0.0));
+        assert_eq!(next, Rect { x: 10.0, y: 55.0, w: 50.0, h: 40.0 });
+    }
+
+    #[test]
+    fn spanning_cell_grows_auto_tracks() {
+        let grid = Grid::new(vec![Track::Auto, Track::Auto, Track::Fixed(40.0)]).spacing(10.0, 0.0);
+        let mut cursor = GridCursor::new(Id::from_key("grid"), &grid, REGION, GridMeasure::default());
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(30.0, 20.0));
+        cursor.request(span(1, 0, 1, 3));
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(200.0, 20.0));
+        // A spanning cell that fits into the tracks does not change them
+        cursor.request(span(2, 0, 1, 2));
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 20.0));
+
+        let (measure, changed) = cursor.finish();
+        assert!(changed);
+        assert_eq!(measure.columns, vec![85.0, 55.0]);
+
+        // Laid out with the measurement, the spanning cell fits exactly
+        let mut cursor = GridCursor::new(Id::from_key("grid"), &grid, REGION, measure);
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(30.0, 20.0));
+        cursor.request(span(1, 0, 1, 3));
+        assert_eq!(cursor.allocate(Sizing::Auto, Sizing::Auto, hint(200.0, 20.0)).w, 200.0);
+        cursor.request(span(2, 0, 1, 2));
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 20.0));
+        assert!(! cursor.finish().1);
+    }
+
+    #[test]
+    fn spanning_rows_grow_auto_rows() {
+        let grid = Grid::new(vec![Track::Fixed(50.0), Track::Fixed(50.0)]).spacing(0.0, 4.0);
+        let mut cursor = GridCursor::new(Id::from_key("grid"), &grid, REGION, GridMeasure::default());
+        cursor.request(span(0, 0, 2, 1));
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(10.0, 64.0));
+        cursor.request(span(0, 1, 1, 1));
+        cursor.allocate(Sizing::Auto, Sizing::Auto, hint(10.0, 20.0));
+
+        let (measure, _) = cursor.finish();
+        assert_eq!(measure.rows, vec![40.0, 20.0]);
+    }
+}
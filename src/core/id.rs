@@ -1,22 +1,4 @@
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-pub struct Id(u64);
-
-impl Id {
-    /// *The* invalid ID. Used where it is necessary to explicitly
-    /// state that no existing widget is referenced.
-    #[inline(always)]
-    pub const fn invalid() -> Id {
-        Id(0xFFFF_FFFF_FFFF_FFFF)
-    }
-}
-
-/// Generate a unique widget ID based on the source location.
-#[macro_export]
-macro_rules! mk_id {
-    () => {
-        $crate::core::make_id(line!(), column!())
-    };
-}
+use std::hash::{Hash, Hasher};
 
 macro_rules! fnv_byte {
     ($hash: ident, $data: expr) => {
@@ -38,12 +20,141 @@ macro_rules! fnv_u32 {
     };
 }
 
-/// Build a widget ID from line and column where the generator macro is used.
-pub const fn make_id(line: u32, column: u32) -> Id {
+/// The place in the source code where an ID was generated.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SourceLocation {
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Identifies a widget across frames.
+///
+/// In debug builds, an ID also remembers where it was generated, which is
+/// used for diagnosing duplicate IDs. Only the hash takes part in comparisons.
+#[derive(Copy, Clone, Debug)]
+pub struct Id {
+    hash: u64,
+    #[cfg(debug_assertions)]
+    source: Option<SourceLocation>,
+}
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Id) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl Eq for Id {}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl Id {
+    /// *The* invalid ID. Used where it is necessary to explicitly
+    /// state that no existing widget is referenced.
+    #[inline(always)]
+    pub const fn invalid() -> Id {
+        Id::from_hash(0xFFFF_FFFF_FFFF_FFFF, None)
+    }
+
+    #[allow(unused_variables)]
+    const fn from_hash(hash: u64, source: Option<SourceLocation>) -> Id {
+        Id {
+            hash,
+            #[cfg(debug_assertions)]
+            source,
+        }
+    }
+
+    /// Build an ID from an arbitrary hashable key, e.g. the label of a widget
+    /// that was not given an explicit ID.
+    pub fn from_key<H: Hash + ?Sized>(key: &H) -> Id {
+        Id::from_hash(0xcbf29ce484222325, None).with(key)
+    }
+
+    /// The location of the `mk_id!` invocation that generated this ID.
+    /// Only available in debug builds.
+    pub fn source(&self) -> Option<SourceLocation> {
+        #[cfg(debug_assertions)]
+        {
+            self.source
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            None
+        }
+    }
+
+    /// Derive a new ID by mixing an arbitrary hashable key into this one,
+    /// e.g. a loop index or the name of an item.
+    pub fn with<H: Hash + ?Sized>(self, key: &H) -> Id {
+        let mut hasher = FnvHasher(self.hash);
+        key.hash(&mut hasher);
+        Id::from_hash(hasher.finish(), self.source())
+    }
+
+    /// Derive the ID of a child widget from the ID of its parent scope.
+    pub fn combine(self, child: Id) -> Id {
+        let mut fnv = self.hash;
+        fnv = fnv_u32!(fnv, child.hash);
+        fnv = fnv_u32!(fnv, child.hash >> 32);
+        Id::from_hash(fnv, child.source())
+    }
+}
+
+/// Generate a unique widget ID based on the source location.
+/// An optional key can be given in order to distinguish widgets
+/// created at the same location, e.g. in a loop.
+#[macro_export]
+macro_rules! mk_id {
+    () => {
+        $crate::core::make_id(file!(), line!(), column!())
+    };
+    ($key: expr) => {
+        $crate::core::make_id(file!(), line!(), column!()).with(&$key)
+    };
+}
+
+/// Build a widget ID from the file, line and column where the generator macro is used.
+pub const fn make_id(file: &'static str, line: u32, column: u32) -> Id {
     let mut fnv = 0xcbf29ce484222325u64;
 
+    let bytes = file.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        fnv = fnv_byte!(fnv, bytes[i]);
+        i += 1;
+    }
+
     fnv = fnv_u32!(fnv, line);
     fnv = fnv_u32!(fnv, column);
 
-    Id(fnv)
-}
\ No newline at end of file
+    Id::from_hash(fnv, Some(SourceLocation { file, line, column }))
+}
+
+/// Feeds arbitrary hashable values into the FNV hash used for widget IDs.
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut fnv = self.0;
+        for byte in bytes {
+            fnv = fnv_byte!(fnv, *byte);
+        }
+        self.0 = fnv;
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,102 @@
+//! The input events glui understands, independently of the windowing library
+//! they originate from. See `Glui::feed_event`.
+
+use super::{Point, Size};
+use serde::{Deserialize, Serialize};
+
+/// The input events that affect the `GluiState`.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum InputEvent {
+    MouseMove(Point),
+    PointerButton(PointerButton, ButtonState),
+    Scroll(ScrollDelta),
+    Key(KeyEvent),
+    Text(char),
+    CloseRequested,
+    Resized(Size),
+    /// The time in seconds since the start of the session, at which the
+    /// following events occurred.
+    Time(f64),
+}
+
+/// Mouse wheel or trackpad scrolling. Positive values move the content to
+/// the bottom right, i.e. scroll towards the top left.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ScrollDelta {
+    /// A number of lines, which are converted to logical units with `GluiState::scroll_line_height`.
+    Lines(Point),
+    /// A distance in logical units.
+    Pixels(Point),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ButtonState {
+    Pressed,
+    Released,
+}
+
+impl ButtonState {
+    pub fn is_pressed(&self) -> bool {
+        *self == ButtonState::Pressed
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum PointerButton {
+    Left,
+    Right,
+    Middle,
+    /// Any additional button, numbered by the windowing library.
+    Other(u8),
+}
+
+/// The modifier keys held down while a key was pressed or released.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The Windows, Command or Super key.
+    pub logo: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers { shift: false, ctrl: false, alt: false, logo: false };
+    pub const SHIFT: Modifiers = Modifiers { shift: true, .. Modifiers::NONE };
+    pub const CTRL: Modifiers = Modifiers { ctrl: true, .. Modifiers::NONE };
+    pub const ALT: Modifiers = Modifiers { alt: true, .. Modifiers::NONE };
+}
+
+/// The keys glui distinguishes, identified by their meaning in the current
+/// keyboard layout. Text is received separately as `InputEvent::Text`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Key {
+    Escape,
+    Tab,
+    Enter,
+    Space,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Left,
+    Right,
+    Up,
+    Down,
+    /// The key opening the context menu.
+    Menu,
+    A, B, C, D, E, F, G, H, I, J, K, L, M,
+    N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Num0, Num1, Num2, Num3, Num4, Num5, Num6, Num7, Num8, Num9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub modifiers: Modifiers,
+    pub state: ButtonState,
+}
@@ -0,0 +1,30 @@
+use super::{DisplayList, Rect};
+use super::scroll::ScrollCursor;
+
+/// The layers the UI is drawn in, from bottom to top.
+///
+/// Everything in a layer is drawn above all lower layers, regardless of the
+/// order in which the widgets were created, and widgets in higher layers
+/// receive the mouse before the widgets below them.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum Layer {
+    Background,
+    /// The layer widgets are created in unless another one is selected.
+    #[default]
+    Normal,
+    /// Menus and dropdown lists that open above the widget they belong to.
+    Popup,
+    Tooltip,
+    /// Modal dialogs and overlays covering everything below them.
+    Modal,
+}
+
+/// The state of the enclosing layer while widgets are created in another one.
+pub(crate) struct LayerCursor {
+    pub layer: Layer,
+    /// The draw commands, interaction clip and scroll areas of the enclosing
+    /// layer, which are restored when the layer is closed.
+    pub parent_list: DisplayList,
+    pub parent_clip: Vec<Rect>,
+    pub parent_scrolls: Vec<ScrollCursor>,
+}
@@ -0,0 +1,563 @@
+use super::{Id, Point, Rect, SizeHint};
+use super::grid::GridCursor;
+use super::constraint::ConstraintCursor;
+use super::split::SplitCursor;
+use super::measure::Extent;
+
+/// How the extent of a widget along one axis is determined.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Sizing {
+    /// Use the natural size of the widget content.
+    Auto,
+    /// A fixed size in logical units.
+    Fixed(f64),
+    /// A fraction of the extent of the enclosing container.
+    Fraction(f64),
+    /// The space left by the other children of the enclosing container, which
+    /// is shared evenly by all children with this sizing.
+    Fill,
+}
+
+/// Where and how large a widget or container should be.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Placement {
+    /// An absolute position, which bypasses the enclosing layout.
+    pub position: Option<Point>,
+    pub width: Sizing,
+    pub height: Sizing,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Placement {
+            position: None,
+            width: Sizing::Auto,
+            height: Sizing::Auto,
+        }
+    }
+}
+
+impl Placement {
+    pub fn at(self, x: f64, y: f64) -> Self {
+        Placement {
+            position: Some(Point { x, y }),
+            .. self
+        }
+    }
+
+    pub fn size(self, w: f64, h: f64) -> Self {
+        Placement {
+            width: Sizing::Fixed(w),
+            height: Sizing::Fixed(h),
+            .. self
+        }
+    }
+
+    pub fn region(self, region: Rect) -> Self {
+        self.at(region.x, region.y).size(region.w, region.h)
+    }
+}
+
+/// The way a container arranges its children.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Arrangement {
+    /// Children are placed from left to right.
+    Row,
+    /// Children are placed from top to bottom.
+    Column,
+    /// Children are placed on top of each other.
+    Stack,
+}
+
+/// Alignment of children along the axis that is not determined by the arrangement,
+/// or along both axes in a stack.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    /// Children with automatic size are stretched to the full extent of the container.
+    Stretch,
+}
+
+/// Describes a container that hands out regions to the widgets created inside of it.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Layout {
+    pub arrangement: Arrangement,
+    /// Space between two consecutive children.
+    pub spacing: f64,
+    /// Space between the border of the container and its children.
+    pub padding: f64,
+    pub align: Align,
+    /// The placement of the container itself within its parent.
+    pub placement: Placement,
+}
+
+impl Layout {
+    pub fn new(arrangement: Arrangement) -> Self {
+        Layout {
+            arrangement,
+            spacing: 0.0,
+            padding: 0.0,
+            align: Align::Start,
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn row() -> Self {
+        Layout::new(Arrangement::Row)
+    }
+
+    pub fn column() -> Self {
+        Layout::new(Arrangement::Column)
+    }
+
+    pub fn stack() -> Self {
+        Layout::new(Arrangement::Stack)
+    }
+
+    pub fn spacing(self, spacing: f64) -> Self {
+        Layout { spacing, .. self }
+    }
+
+    pub fn padding(self, padding: f64) -> Self {
+        Layout { padding, .. self }
+    }
+
+    pub fn align(self, align: Align) -> Self {
+        Layout { align, .. self }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+}
+
+/// How the children of a row or column shared its main axis in a layout pass.
+/// The space left by the other children is split evenly between the children
+/// with `Sizing::Fill` based on the measurement of the previous pass.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub(crate) struct FillMeasure {
+    /// The extent of all children without `Sizing::Fill`, including spacing.
+    pub rigid: f64,
+    /// The number of children with `Sizing::Fill`.
+    pub fills: usize,
+}
+
+/// The state of a row, column or stack while its children are being created.
+pub(crate) struct LinearCursor {
+    id: Id,
+    arrangement: Arrangement,
+    spacing: f64,
+    padding: f64,
+    align: Align,
+    /// The region available to the children.
+    content: Rect,
+    /// The extent along the main axis that has been used by previous children.
+    used: f64,
+    /// The number of children placed so far.
+    children: usize,
+    /// The size of the children measured so far along the main and cross axis.
+    measured: (Extent, Extent),
+    /// How the main axis was shared in the previous pass.
+    previous: FillMeasure,
+    /// How the main axis has been shared by the children placed so far.
+    fill: FillMeasure,
+    /// The extent given to the children with `Sizing::Fill` so far.
+    filled: f64,
+}
+
+impl LinearCursor {
+    pub fn new(id: Id, layout: &Layout, region: Rect, previous: FillMeasure) -> Self {
+        LinearCursor {
+            id,
+            arrangement: layout.arrangement,
+            spacing: layout.spacing,
+            padding: layout.padding,
+            align: layout.align,
+            content: shrink(region, layout.padding),
+            used: 0.0,
+            children: 0,
+            measured: (Extent::fixed(0.0), Extent::fixed(0.0)),
+            previous,
+            fill: FillMeasure::default(),
+            filled: 0.0,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Insert additional space before the next child.
+    pub fn add_space(&mut self, amount: f64) {
+        self.used += amount;
+        self.fill.rigid += amount;
+        self.measured.0 = self.measured.0.grow(amount);
+    }
+
+    /// The region that is still available for the next child.
+    pub fn available(&self) -> Rect {
+        let offset = self.next_offset();
+        match self.arrangement {
+            Arrangement::Row => Rect {
+                x: self.content.x + offset,
+                w: (self.content.w - offset).max(0.0),
+                .. self.content
+            },
+            Arrangement::Column => Rect {
+                y: self.content.y + offset,
+                h: (self.content.h - offset).max(0.0),
+                .. self.content
+            },
+            Arrangement::Stack => self.content,
+        }
+    }
+
+    /// Hand out the region for the next child.
+    pub fn allocate(&mut self, width: Sizing, height: Sizing, hint: SizeHint) -> Rect {
+        let available = self.available();
+        let horizontal = contribution(width, hint.horizontal());
+        let vertical = contribution(height, hint.vertical());
+        if self.children > 0 && self.arrangement != Arrangement::Stack {
+            self.fill.rigid += self.spacing;
+        }
+
+        let rect = match self.arrangement {
+            Arrangement::Row => {
+                let w = self.resolve_main(width, hint.horizontal(), self.content.w, available.w);
+                let (y, h) = align_in(height, hint.vertical(), self.content.y, self.content.h, self.align);
+                Rect { x: available.x, y, w, h }
+            }
+            Arrangement::Column => {
+                let h = self.resolve_main(height, hint.vertical(), self.content.h, available.h);
+                let (x, w) = align_in(width, hint.horizontal(), self.content.x, self.content.w, self.align);
+                Rect { x, y: available.y, w, h }
+            }
+            Arrangement::Stack => {
+                let (x, w) = align_in(width, hint.horizontal(), self.content.x, self.content.w, self.align);
+                let (y, h) = align_in(height, hint.vertical(), self.content.y, self.content.h, self.align);
+                Rect { x, y, w, h }
+            }
+        };
+
+        let spacing = if self.children == 0 { 0.0 } else { self.spacing };
+        let (main, cross) = self.measured;
+        self.measured = match self.arrangement {
+            Arrangement::Row => (main.then(horizontal, spacing), cross.union(vertical)),
+            Arrangement::Column => (main.then(vertical, spacing), cross.union(horizontal)),
+            Arrangement::Stack => (main.union(horizontal), cross.union(vertical)),
+        };
+
+        match self.arrangement {
+            Arrangement::Row => self.used = rect.x + rect.w - self.content.x,
+            Arrangement::Column => self.used = rect.y + rect.h - self.content.y,
+            Arrangement::Stack => {},
+        }
+        self.children += 1;
+
+        rect
+    }
+
+    /// The size of the container that fits all children placed so far.
+    pub fn measure(&self) -> SizeHint {
+        let (main, cross) = self.measured;
+        let (horizontal, vertical) = match self.arrangement {
+            Arrangement::Column => (cross, main),
+            Arrangement::Row | Arrangement::Stack => (main, cross),
+        };
+        SizeHint::from_extents(horizontal.grow(2.0 * self.padding), vertical.grow(2.0 * self.padding))
+    }
+
+    /// How the main axis was shared by the children placed so far.
+    pub fn fill_measure(&self) -> FillMeasure {
+        self.fill
+    }
+
+    /// Compute the extent along the axis in which children are placed one after
+    /// another, and record it for the next pass.
+    fn resolve_main(&mut self, sizing: Sizing, hint: Extent, extent: f64, available: f64) -> f64 {
+        let size = match sizing {
+            Sizing::Auto => hint.preferred,
+            Sizing::Fixed(size) => size,
+            Sizing::Fraction(fraction) => fraction * extent,
+            Sizing::Fill => {
+                // Without a previous measurement, the child takes all that is left
+                let remaining_fills = self.previous.fills.saturating_sub(self.fill.fills);
+                let share = if remaining_fills == 0 {
+                    available
+                } else {
+                    let remaining = extent - self.previous.rigid - self.filled;
+                    (remaining / remaining_fills as f64).clamp(0.0, available)
+                };
+                let size = hint.clamp(share);
+                self.fill.fills += 1;
+                self.filled += size;
+                return size;
+            }
+        };
+        self.fill.rigid += size;
+        size
+    }
+
+    fn next_offset(&self) -> f64 {
+        if self.children == 0 {
+            self.used
+        } else {
+            self.used + self.spacing
+        }
+    }
+}
+
+/// The state of any kind of container while its children are being created.
+pub(crate) enum LayoutCursor {
+    Linear(LinearCursor),
+    Grid(Box<GridCursor>),
+    Constraint(Box<ConstraintCursor>),
+    Split(SplitCursor),
+}
+
+impl LayoutCursor {
+    pub fn id(&self) -> Id {
+        match self {
+            LayoutCursor::Linear(cursor) => cursor.id(),
+            LayoutCursor::Grid(cursor) => cursor.id(),
+            LayoutCursor::Constraint(cursor) => cursor.id(),
+            LayoutCursor::Split(cursor) => cursor.id(),
+        }
+    }
+
+    /// The number of children placed so far.
+    pub fn children(&self) -> usize {
+        match self {
+            LayoutCursor::Linear(cursor) => cursor.children,
+            LayoutCursor::Grid(cursor) => cursor.children(),
+            LayoutCursor::Constraint(cursor) => cursor.children(),
+            LayoutCursor::Split(cursor) => cursor.children(),
+        }
+    }
+
+    pub fn add_space(&mut self, amount: f64) {
+        match self {
+            LayoutCursor::Linear(cursor) => cursor.add_space(amount),
+            LayoutCursor::Grid(_) | LayoutCursor::Constraint(_) | LayoutCursor::Split(_) => {},
+        }
+    }
+
+    pub fn available(&self) -> Rect {
+        match self {
+            LayoutCursor::Linear(cursor) => cursor.available(),
+            LayoutCursor::Grid(cursor) => cursor.available(),
+            LayoutCursor::Constraint(cursor) => cursor.available(),
+            LayoutCursor::Split(cursor) => cursor.available(),
+        }
+    }
+
+    pub fn allocate(&mut self, width: Sizing, height: Sizing, hint: SizeHint) -> Rect {
+        match self {
+            LayoutCursor::Linear(cursor) => cursor.allocate(width, height, hint),
+            LayoutCursor::Grid(cursor) => cursor.allocate(width, height, hint),
+            LayoutCursor::Constraint(cursor) => cursor.allocate(width, height, hint),
+            LayoutCursor::Split(cursor) => cursor.allocate(width, height, hint),
+        }
+    }
+}
+
+/// Compute position and extent of a child along an axis on which it is aligned
+/// within the given extent.
+pub(crate) fn align_in(sizing: Sizing, hint: Extent, start: f64, extent: f64, align: Align) -> (f64, f64) {
+    let size = match sizing {
+        Sizing::Auto if align == Align::Stretch => hint.clamp(extent),
+        Sizing::Auto => hint.preferred,
+        Sizing::Fixed(size) => size,
+        Sizing::Fraction(fraction) => fraction * extent,
+        Sizing::Fill => hint.clamp(extent),
+    };
+
+    let position = match align {
+        Align::Start | Align::Stretch => start,
+        Align::Center => start + (extent - size) / 2.0,
+        Align::End => start + extent - size,
+    };
+
+    (position, size)
+}
+
+/// The extent a child with the given sizing contributes to the size of its container.
+pub(crate) fn contribution(sizing: Sizing, hint: Extent) -> Extent {
+    match sizing {
+        Sizing::Fixed(size) => Extent::fixed(size),
+        Sizing::Auto | Sizing::Fraction(_) | Sizing::Fill => hint,
+    }
+}
+
+/// Remove the padding from all sides of the region.
+pub(crate) fn shrink(region: Rect, padding: f64) -> Rect {
+    Rect {
+        x: region.x + padding,
+        y: region.y + padding,
+        w: (region.w - 2.0 * padding).max(0.0),
+        h: (region.h - 2.0 * padding).max(0.0),
+    }
+}
+
+/// Resolve a placement relative to the given region, ignoring any enclosing layout.
+pub(crate) fn resolve_absolute(placement: &Placement, region: Rect, hint: SizeHint) -> Rect {
+    let origin = placement.position.unwrap_or_else(|| region.top_left());
+    let resolve = |sizing, hint: Extent, extent: f64, offset: f64| match sizing {
+        Sizing::Auto => hint.preferred,
+        Sizing::Fixed(size) => size,
+        Sizing::Fraction(fraction) => fraction * extent,
+        Sizing::Fill => hint.clamp((extent - offset).max(0.0)),
+    };
+
+    Rect {
+        x: origin.x,
+        y: origin.y,
+        w: resolve(placement.width, hint.horizontal(), region.w, origin.x - region.x),
+        h: resolve(placement.height, hint.vertical(), region.h, origin.y - region.y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Size;
+
+    const REGION: Rect = Rect { x: 10.0, y: 20.0, w: 300.0, h: 100.0 };
+
+    fn hint(w: f64, h: f64) -> SizeHint {
+        SizeHint::flexible(Size { w, h })
+    }
+
+    fn cursor(layout: Layout) -> LinearCursor {
+        LinearCursor::new(Id::from_key("layout"), &layout, REGION, FillMeasure::default())
+    }
+
+    /// Place the children twice, the second time with the measurement of the first pass.
+    fn allocate_twice(layout: Layout, children: &[(Sizing, Sizing, SizeHint)]) -> Vec<Rect> {
+        let mut first = cursor(layout);
+        for (width, height, hint) in children {
+            first.allocate(*width, *height, *hint);
+        }
+        let mut second = LinearCursor::new(Id::from_key("layout"), &layout, REGION, first.fill_measure());
+        let rects = children.iter().map(|(width, height, hint)| second.allocate(*width, *height, *hint)).collect();
+        // The measurement is stable, such that no further pass is needed
+        assert_eq!(second.fill_measure(), first.fill_measure());
+        rects
+    }
+
+    #[test]
+    fn row_places_children_after_each_other() {
+        let mut row = cursor(Layout::row().padding(5.0).spacing(4.0));
+        let first = row.allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 10.0));
+        let second = row.allocate(Sizing::Fixed(30.0), Sizing::Fixed(20.0), hint(50.0, 10.0));
+        assert_eq!(first, Rect { x: 15.0, y: 25.0, w: 50.0, h: 10.0 });
+        assert_eq!(second, Rect { x: 69.0, y: 25.0, w: 30.0, h: 20.0 });
+        assert_eq!(row.available(), Rect { x: 103.0, y: 25.0, w: 202.0, h: 90.0 });
+        assert_eq!(row.measure().preferred, Size { w: 94.0, h: 30.0 });
+    }
+
+    #[test]
+    fn column_places_children_below_each_other() {
+        let mut column = cursor(Layout::column().padding(5.0).spacing(4.0));
+        column.allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 10.0));
+        column.add_space(6.0);
+        let second = column.allocate(Sizing::Auto, Sizing::Auto, hint(80.0, 10.0));
+        assert_eq!(second, Rect { x: 15.0, y: 45.0, w: 80.0, h: 10.0 });
+        assert_eq!(column.measure().preferred, Size { w: 90.0, h: 40.0 });
+    }
+
+    #[test]
+    fn stack_places_children_on_top_of_each_other() {
+        let mut stack = cursor(Layout::stack().align(Align::Center));
+        let first = stack.allocate(Sizing::Auto, Sizing::Auto, hint(100.0, 40.0));
+        let second = stack.allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 60.0));
+        assert_eq!(first, Rect { x: 110.0, y: 50.0, w: 100.0, h: 40.0 });
+        assert_eq!(second, Rect { x: 135.0, y: 40.0, w: 50.0, h: 60.0 });
+        assert_eq!(stack.measure().preferred, Size { w: 100.0, h: 60.0 });
+    }
+
+    #[test]
+    fn align_on_cross_axis() {
+        let place = |align| cursor(Layout::row().align(align)).allocate(Sizing::Auto, Sizing::Auto, hint(50.0, 40.0));
+        assert_eq!(place(Align::Start), Rect { x: 10.0, y: 20.0, w: 50.0, h: 40.0 });
+        assert_eq!(place(Align::Center), Rect { x: 10.0, y: 50.0, w: 50.0, h: 40.0 });
+        assert_eq!(place(Align::End), Rect { x: 10.0, y: 80.0, w: 50.0, h: 40.0 });
+        assert_eq!(place(Align::Stretch), Rect { x: 10.0, y: 20.0, w: 50.0, h: 100.0 });
+
+        // Stretching does not apply to fixed sizes
+        let fixed = cursor(Layout::column().align(Align::Stretch)).allocate(Sizing::Fixed(50.0), Sizing::Auto, hint(80.0, 40.0));
+        assert_eq!(fixed, Rect { x: 10.0, y: 20.0, w: 50.0, h: 40.0 });
+    }
+
+    #[test]
+    fn fraction_of_content() {
+        let mut row = cursor(Layout::row().padding(10.0));
+        let rect = row.allocate(Sizing::Fraction(0.5), Sizing::Fraction(0.25), hint(0.0, 0.0));
+        assert_eq!(rect, Rect { x: 20.0, y: 30.0, w: 140.0, h: 20.0 });
+    }
+
+    #[test]
+    fn fill_takes_remaining_space_without_measurement() {
+        let mut row = cursor(Layout::row().spacing(10.0));
+        row.allocate(Sizing::Fixed(50.0), Sizing::Auto, hint(0.0, 0.0));
+        let fill = row.allocate(Sizing::Fill, Sizing::Auto, hint(0.0, 0.0));
+        assert_eq!(fill, Rect { x: 70.0, y: 20.0, w: 240.0, h: 0.0 });
+    }
+
+    #[test]
+    fn fill_leaves_room_for_later_children() {
+        // A label, a text box filling the row and a button
+        let rects = allocate_twice(Layout::row().spacing(5.0), &[
+            (Sizing::Auto, Sizing::Auto, hint(50.0, 20.0)),
+            (Sizing::Fill, Sizing::Auto, hint(100.0, 20.0)),
+            (Sizing::Fixed(40.0), Sizing::Auto, hint(0.0, 20.0)),
+        ]);
+        assert_eq!(rects[1], Rect { x: 65.0, y: 20.0, w: 200.0, h: 20.0 });
+        assert_eq!(rects[2], Rect { x: 270.0, y: 20.0, w: 40.0, h: 20.0 });
+    }
+
+    #[test]
+    fn fill_children_share_remaining_space() {
+        let rects = allocate_twice(Layout::column().padding(10.0).spacing(4.0), &[
+            (Sizing::Auto, Sizing::Fill, hint(0.0, 0.0)),
+            (Sizing::Auto, Sizing::Fixed(12.0), hint(0.0, 0.0)),
+            (Sizing::Auto, Sizing::Fill, hint(0.0, 0.0)),
+        ]);
+        assert_eq!(rects.iter().map(|rect| (rect.y, rect.h)).collect::<Vec<_>>(), vec![(30.0, 30.0), (64.0, 12.0), (80.0, 30.0)]);
+
+        // A fill child that cannot shrink any further keeps its minimum size
+        let rects = allocate_twice(Layout::row(), &[
+            (Sizing::Fill, Sizing::Auto, SizeHint::at_least(Size { w: 200.0, h: 0.0 })),
+            (Sizing::Fill, Sizing::Auto, hint(0.0, 0.0)),
+        ]);
+        assert_eq!(rects[0].w, 200.0);
+        assert_eq!(rects[1], Rect { x: 210.0, y: 20.0, w: 100.0, h: 0.0 });
+    }
+}
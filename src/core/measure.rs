@@ -0,0 +1,155 @@
+use super::Size;
+
+/// The sizes a widget or container can sensibly be laid out with, as reported
+/// during the measure pass.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SizeHint {
+    /// The size below which the content no longer fits.
+    pub min: Size,
+    /// The size used for automatically sized widgets.
+    pub preferred: Size,
+    /// The size beyond which the widget does not grow when filling its container.
+    pub max: Size,
+}
+
+impl Default for SizeHint {
+    fn default() -> Self {
+        SizeHint::flexible(Size::zero())
+    }
+}
+
+impl From<Size> for SizeHint {
+    fn from(preferred: Size) -> Self {
+        SizeHint::at_least(preferred)
+    }
+}
+
+impl SizeHint {
+    pub fn new(min: Size, preferred: Size, max: Size) -> Self {
+        SizeHint { min, preferred, max }
+    }
+
+    /// Content that prefers the given size and must not be made any smaller,
+    /// but may grow without bounds.
+    pub fn at_least(preferred: Size) -> Self {
+        SizeHint {
+            min: preferred,
+            preferred,
+            max: Size { w: f64::INFINITY, h: f64::INFINITY },
+        }
+    }
+
+    /// Content that prefers the given size but can be shrunk or grown arbitrarily.
+    pub fn flexible(preferred: Size) -> Self {
+        SizeHint {
+            min: Size::zero(),
+            .. SizeHint::at_least(preferred)
+        }
+    }
+
+    /// Content of exactly the given size.
+    pub fn fixed(size: Size) -> Self {
+        SizeHint {
+            min: size,
+            preferred: size,
+            max: size,
+        }
+    }
+
+    /// Raise the minimum (and if necessary the preferred) width.
+    pub fn min_width(mut self, w: f64) -> Self {
+        self.min.w = self.min.w.max(w);
+        self.preferred.w = self.preferred.w.max(w);
+        self.max.w = self.max.w.max(w);
+        self
+    }
+
+    /// Raise the minimum (and if necessary the preferred) height.
+    pub fn min_height(mut self, h: f64) -> Self {
+        self.min.h = self.min.h.max(h);
+        self.preferred.h = self.preferred.h.max(h);
+        self.max.h = self.max.h.max(h);
+        self
+    }
+
+    /// Restrict a size to the range between `min` and `max`.
+    pub fn clamp(&self, size: Size) -> Size {
+        Size {
+            w: self.horizontal().clamp(size.w),
+            h: self.vertical().clamp(size.h),
+        }
+    }
+
+    pub(crate) fn from_extents(horizontal: Extent, vertical: Extent) -> Self {
+        SizeHint {
+            min: Size { w: horizontal.min, h: vertical.min },
+            preferred: Size { w: horizontal.preferred, h: vertical.preferred },
+            max: Size { w: horizontal.max, h: vertical.max },
+        }
+    }
+
+    pub(crate) fn horizontal(&self) -> Extent {
+        Extent {
+            min: self.min.w,
+            preferred: self.preferred.w,
+            max: self.max.w,
+        }
+    }
+
+    pub(crate) fn vertical(&self) -> Extent {
+        Extent {
+            min: self.min.h,
+            preferred: self.preferred.h,
+            max: self.max.h,
+        }
+    }
+}
+
+/// The sizes of a `SizeHint` along a single axis.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Extent {
+    pub min: f64,
+    pub preferred: f64,
+    pub max: f64,
+}
+
+impl Extent {
+    pub fn fixed(size: f64) -> Self {
+        Extent { min: size, preferred: size, max: size }
+    }
+
+    pub fn clamp(self, size: f64) -> f64 {
+        size.min(self.max).max(self.min)
+    }
+
+    /// The extent of two pieces of content placed one after another.
+    pub fn then(self, other: Extent, spacing: f64) -> Self {
+        Extent {
+            min: self.min + spacing + other.min,
+            preferred: self.preferred + spacing + other.preferred,
+            max: self.max + spacing + other.max,
+        }
+    }
+
+    /// The extent of two pieces of content placed next to each other.
+    pub fn union(self, other: Extent) -> Self {
+        Extent {
+            min: self.min.max(other.min),
+            preferred: self.preferred.max(other.preferred),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn grow(self, amount: f64) -> Self {
+        Extent {
+            min: self.min + amount,
+            preferred: self.preferred + amount,
+            max: self.max + amount,
+        }
+    }
+}
+
+/// The size hint measured for a container, which is used to lay out the
+/// container in the next pass. It is kept in the container's widget state.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Measured(pub SizeHint);
@@ -1,18 +1,72 @@
 mod util;
 mod id;
 mod state;
+mod input;
 mod frame;
 mod style;
+mod renderer;
+mod display_list;
+mod duplicates;
+mod layout;
+mod grid;
+mod measure;
+mod constraint;
+mod scroll;
+mod split;
+mod window;
+mod layer;
+mod response;
+mod widget;
+mod widget_state;
 
 pub use id::*;
 pub use util::*;
 pub use frame::*;
 pub use state::*;
+pub use input::*;
 pub use style::*;
+pub use renderer::*;
+pub use display_list::*;
+pub use duplicates::DuplicateId;
+pub use layout::{Sizing, Placement, Arrangement, Align, Layout};
+pub use grid::{Grid, Track};
+pub use measure::SizeHint;
+pub use scroll::ScrollArea;
+pub use split::{Orientation, Splitter};
+pub use window::Window;
+pub use layer::Layer;
+pub use response::{Response, Sense};
+pub use widget::Widget;
+pub use constraint::{Edge, Anchors, Anchor, Expression, Relation, Strength, Constraint, ConstraintLayout};
 
 pub struct Glui {
     pub uistate: GluiState,
     pub style: Style,
+    pub debug: DebugOptions,
+    /// The number of frames after which the state of a widget that was not
+    /// shown is dropped, see `GluiFrame::state`.
+    pub state_lifetime: u64,
+    /// Duplicate IDs that have already been logged, in order to report each one
+    /// only once while it stays duplicated.
+    #[cfg(debug_assertions)]
+    reported_duplicates: std::collections::HashSet<Id>,
+    /// The state of widgets and containers kept across frames, including the
+    /// measurements of layout containers and the solvers of constraint layouts.
+    states: widget_state::WidgetStates,
+    windows: window::WindowStack,
+}
+
+/// Diagnostics that are only active in debug builds.
+#[derive(Default)]
+pub struct DebugOptions {
+    /// Outline widgets that share the same ID in red.
+    pub highlight_duplicate_ids: bool,
+}
+
+impl Default for Glui {
+    fn default() -> Self {
+        Glui::new()
+    }
 }
 
 impl Glui {
@@ -21,7 +75,71 @@ impl Glui {
         Glui {
             uistate: GluiState::new(),
             style: Style::default(),
+            debug: DebugOptions::default(),
+            state_lifetime: 120,
+            #[cfg(debug_assertions)]
+            reported_duplicates: std::collections::HashSet::new(),
+            states: widget_state::WidgetStates::default(),
+            windows: window::WindowStack::default(),
+        }
+    }
+
+    /// Apply an input event received by the host, which takes effect in the next frame.
+    pub fn feed_event(&mut self, event: InputEvent) {
+        self.uistate.apply(&event);
+    }
+
+    /// Build a frame by calling `ui` in one or more layout passes, and return
+    /// the output of the last pass, which is the one to display.
+    ///
+    /// As long as containers change their measured size, the frame is laid out
+    /// again, at most `MAX_LAYOUT_PASSES` times. The input that arrived since
+    /// the previous frame is held back until the layout is stable and only
+    /// delivered to the last pass, such that `ui` reacts to each event once.
+    pub fn run_frame<R, T, F>(&mut self, renderer: &mut R, mut ui: F) -> FrameResult<T> where
+        R: Renderer,
+        F: FnMut(&mut GluiFrame<R>) -> T
+    {
+        let mut held = self.uistate.hold_input();
+        let has_input = held.is_some();
+        let mut redraw = false;
+        let mut pass = 1;
+
+        let (value, display_list, duplicate_ids) = loop {
+            let mut frame = GluiFrame::begin(self, renderer);
+            let value = ui(&mut frame);
+            redraw |= frame.requested_redraw();
+            let relayout = frame.requested_relayout();
+            let (display_list, duplicate_ids) = frame.end();
+
+            match held.take() {
+                // The last pass is reserved for the input
+                Some(input) if ! relayout || pass + 1 == MAX_LAYOUT_PASSES => self.uistate.release_input(input),
+                Some(input) => held = Some(input),
+                None if ! relayout || pass == MAX_LAYOUT_PASSES || has_input => {
+                    // Building the frame again would deliver the input twice,
+                    // so a layout changed by it is only shown in the next frame
+                    redraw |= relayout;
+                    break (value, display_list, duplicate_ids);
+                }
+                None => {}
+            }
+            pass += 1;
+        };
+
+        self.uistate.end();
+        self.states.end_frame(self.state_lifetime);
+
+        FrameResult {
+            value,
+            display_list,
+            redraw,
+            duplicate_ids,
         }
     }
-    
+
+    /// The state of the given type stored for the widget with the given ID, see `GluiFrame::state`.
+    pub fn state<T: 'static>(&self, id: Id) -> Option<&T> {
+        self.states.peek(id)
+    }
 }
\ No newline at end of file
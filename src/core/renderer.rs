@@ -0,0 +1,40 @@
+use super::{Color, Point, Rect, Size};
+
+/// Selects the font face and size used for rendering or measuring text.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Font<'s> {
+    /// Name under which the font was registered with the backend.
+    pub name: &'s str,
+    pub size: f32,
+}
+
+/// The drawing primitives used by the widgets.
+///
+/// A `GluiFrame` forwards all drawing requests of the widgets to an implementation
+/// of this trait, which allows glui to render with different backends.
+pub trait Renderer {
+    /// Fill a rectangle with rounded corners. A radius of zero yields sharp corners.
+    fn fill_rect(&mut self, rect: Rect, radius: f32, color: Color);
+
+    /// Draw the outline of a rectangle with rounded corners.
+    fn stroke_rect(&mut self, rect: Rect, radius: f32, width: f32, color: Color);
+
+    /// Draw a straight line between two points.
+    fn stroke_line(&mut self, from: Point, to: Point, width: f32, color: Color);
+
+    /// Restrict all following drawing operations to the given rectangle,
+    /// intersected with the currently active clip region.
+    fn push_clip(&mut self, rect: Rect);
+
+    /// Restore the clip region that was active before the last `push_clip`.
+    fn pop_clip(&mut self);
+
+    /// Draw a single line of text whose top left corner is at the given position.
+    fn text(&mut self, text: &str, position: Point, font: Font, color: Color);
+
+    /// Measure the bounding box of a single line of text.
+    fn text_bounds(&mut self, text: &str, font: Font) -> Size;
+
+    /// Compute the x coordinate of each glyph in the text when drawn at the given position.
+    fn glyph_positions(&mut self, text: &str, position: Point, font: Font) -> Vec<f64>;
+}
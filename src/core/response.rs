@@ -0,0 +1,73 @@
+use super::{PointerButton, Point, Rect};
+
+/// The kinds of interaction a widget reacts to, see `GluiFrame::interact`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Sense {
+    /// Whether the widget can be pressed, clicked and dragged with the mouse.
+    pub click: bool,
+    /// Whether the widget can take the keyboard focus, which it does when clicked.
+    pub focus: bool,
+}
+
+impl Sense {
+    /// The widget is only hovered, but still hides the widgets below it from the mouse.
+    pub fn hover() -> Self {
+        Sense { click: false, focus: false }
+    }
+
+    pub fn click() -> Self {
+        Sense { click: true, focus: false }
+    }
+
+    /// The widget can be clicked and focused.
+    pub fn focus() -> Self {
+        Sense { click: true, focus: true }
+    }
+}
+
+/// How the user interacted with a widget in the current frame.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Response {
+    /// The region of the widget.
+    pub rect: Rect,
+    /// Whether the widget is the topmost one below the mouse.
+    pub hovered: bool,
+    /// Whether the left mouse button was pressed on the widget and is still held down.
+    pub pressed: bool,
+    /// The mouse buttons that were released above the widget after pressing
+    /// it, which are checked with `clicked` and `clicked_by`.
+    pub clicks: Vec<PointerButton>,
+    /// Whether the widget was clicked with the left mouse button for the second time in quick succession.
+    pub double_clicked: bool,
+    /// The distance the mouse moved since the previous frame while the widget is pressed.
+    pub drag_delta: Point,
+    pub has_focus: bool,
+    pub gained_focus: bool,
+    pub lost_focus: bool,
+    /// Whether the widget changed the value it edits.
+    pub changed: bool,
+    /// Whether a context menu should be opened for the widget, i.e. it was
+    /// clicked with the right mouse button.
+    pub context_menu: bool,
+}
+
+impl Response {
+    /// Whether the widget was clicked with the left mouse button or activated with the keyboard.
+    pub fn clicked(&self) -> bool {
+        self.clicked_by(PointerButton::Left)
+    }
+
+    pub fn clicked_by(&self, button: PointerButton) -> bool {
+        self.clicks.contains(&button)
+    }
+
+    /// Whether the widget was clicked with the right mouse button.
+    pub fn secondary_clicked(&self) -> bool {
+        self.clicked_by(PointerButton::Right)
+    }
+
+    /// Whether the widget is being dragged with the left mouse button.
+    pub fn dragged(&self) -> bool {
+        self.pressed && self.drag_delta != Point::zero()
+    }
+}
@@ -0,0 +1,360 @@
+use super::{Id, Layout, Placement, Point, Rect, Size, Sizing};
+
+/// Describes a container that shows a part of content which may be larger than
+/// the container itself, and that can be scrolled with the mouse wheel or by
+/// dragging its scrollbars.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ScrollArea {
+    /// The layout of the content. Its placement is ignored, the content is as
+    /// large as the scroll area or as its children, whatever is larger.
+    pub content: Layout,
+    pub horizontal: bool,
+    pub vertical: bool,
+    /// The placement of the scroll area itself within its parent.
+    pub placement: Placement,
+}
+
+impl Default for ScrollArea {
+    fn default() -> Self {
+        ScrollArea::new()
+    }
+}
+
+impl ScrollArea {
+    /// A scroll area that scrolls its content vertically, arranged in a column.
+    pub fn new() -> Self {
+        ScrollArea {
+            content: Layout::column(),
+            horizontal: false,
+            vertical: true,
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn content(self, content: Layout) -> Self {
+        ScrollArea { content, .. self }
+    }
+
+    pub fn horizontal(self, horizontal: bool) -> Self {
+        ScrollArea { horizontal, .. self }
+    }
+
+    pub fn vertical(self, vertical: bool) -> Self {
+        ScrollArea { vertical, .. self }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+}
+
+/// The state of a scroll area that is kept across frames.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct ScrollState {
+    pub offset: Point,
+    /// The size of the content measured in the previous layout pass.
+    pub content: Size,
+    /// The mouse position and scroll offset when dragging a scrollbar thumb started.
+    pub drag: Option<(Point, Point)>,
+    /// The offset the area is currently scrolling to.
+    pub target: Option<Point>,
+}
+
+impl Default for ScrollState {
+    fn default() -> Self {
+        ScrollState {
+            offset: Point::zero(),
+            content: Size::zero(),
+            drag: None,
+            target: None,
+        }
+    }
+}
+
+/// The state of a scroll area while its children are being created.
+pub(crate) struct ScrollCursor {
+    pub id: Id,
+    pub state: ScrollState,
+    /// The directions in which the content can be scrolled.
+    pub horizontal: bool,
+    pub vertical: bool,
+    /// The region in which the content is visible.
+    pub viewport: Rect,
+    pub horizontal_bar: bool,
+    pub vertical_bar: bool,
+}
+
+impl ScrollCursor {
+    pub fn new(id: Id, area: &ScrollArea, region: Rect, state: ScrollState, bar_width: f64) -> Self {
+        // Scrollbars take away space from the viewport, which in turn may
+        // require a scrollbar in the other direction
+        let mut horizontal_bar = false;
+        let mut vertical_bar = false;
+        for _ in 0 .. 2 {
+            let viewport = viewport(region, horizontal_bar, vertical_bar, bar_width);
+            horizontal_bar = area.horizontal && state.content.w > viewport.w;
+            vertical_bar = area.vertical && state.content.h > viewport.h;
+        }
+        let viewport = viewport(region, horizontal_bar, vertical_bar, bar_width);
+
+        let mut cursor = ScrollCursor {
+            id,
+            state,
+            horizontal: area.horizontal,
+            vertical: area.vertical,
+            viewport,
+            horizontal_bar,
+            vertical_bar,
+        };
+        cursor.set_offset(state.offset);
+        cursor
+    }
+
+    /// The region of the content, moved by the scroll offset.
+    pub fn content_region(&self) -> Rect {
+        Rect {
+            x: self.viewport.x - self.state.offset.x,
+            y: self.viewport.y - self.state.offset.y,
+            w: if self.horizontal { self.viewport.w.max(self.state.content.w) } else { self.viewport.w },
+            h: if self.vertical { self.viewport.h.max(self.state.content.h) } else { self.viewport.h },
+        }
+    }
+
+    /// Whether the content is larger than the viewport in any scrollable direction.
+    pub fn can_scroll(&self) -> bool {
+        self.horizontal_bar || self.vertical_bar
+    }
+
+    /// Scroll by the given distance, with positive values moving the content
+    /// to the bottom right.
+    pub fn scroll_by(&mut self, delta: Point) {
+        self.set_offset(Point {
+            x: self.state.offset.x - delta.x,
+            y: self.state.offset.y - delta.y,
+        });
+    }
+
+    /// The track and thumb of the horizontal scrollbar, if shown.
+    pub fn horizontal_scrollbar(&self, bar_width: f64) -> Option<(Rect, Rect)> {
+        if ! self.horizontal_bar {
+            return None;
+        }
+        let track = Rect {
+            x: self.viewport.x,
+            y: self.viewport.y + self.viewport.h,
+            w: self.viewport.w,
+            h: bar_width,
+        };
+        let (x, w) = thumb(track.x, track.w, self.viewport.w, self.state.content.w, self.state.offset.x);
+        Some((track, Rect { x, w, .. track }))
+    }
+
+    /// The track and thumb of the vertical scrollbar, if shown.
+    pub fn vertical_scrollbar(&self, bar_width: f64) -> Option<(Rect, Rect)> {
+        if ! self.vertical_bar {
+            return None;
+        }
+        let track = Rect {
+            x: self.viewport.x + self.viewport.w,
+            y: self.viewport.y,
+            w: bar_width,
+            h: self.viewport.h,
+        };
+        let (y, h) = thumb(track.y, track.h, self.viewport.h, self.state.content.h, self.state.offset.y);
+        Some((track, Rect { y, h, .. track }))
+    }
+
+    pub fn set_offset(&mut self, offset: Point) {
+        self.state.offset = self.clamp(offset);
+    }
+
+    /// Start scrolling towards the offset at which the region is fully visible,
+    /// or as much of it as possible if it is larger than the viewport.
+    pub fn reveal(&mut self, region: Rect) {
+        // The region was placed with the current offset
+        let offset = self.state.offset;
+        let shift = |start: f64, length: f64, view_start: f64, view_length: f64| {
+            if start < view_start || length > view_length {
+                start - view_start
+            } else if start + length > view_start + view_length {
+                start + length - (view_start + view_length)
+            } else {
+                0.0
+            }
+        };
+        let target = self.clamp(Point {
+            x: offset.x + shift(region.x, region.w, self.viewport.x, self.viewport.w),
+            y: offset.y + shift(region.y, region.h, self.viewport.y, self.viewport.h),
+        });
+
+        if target != self.state.offset {
+            self.state.target = Some(target);
+        }
+    }
+
+    /// Move the offset towards the target of a running animation.
+    /// Returns whether the offset changed, in which case another frame is
+    /// needed to show the content at the new offset.
+    pub fn animate(&mut self) -> bool {
+        let target = match self.state.target {
+            Some(target) => self.clamp(target),
+            None => return false,
+        };
+        let offset = self.state.offset;
+        let remaining = Point { x: target.x - offset.x, y: target.y - offset.y };
+
+        if remaining.x.abs() < SNAP_DISTANCE && remaining.y.abs() < SNAP_DISTANCE {
+            self.state.offset = target;
+            self.state.target = None;
+            offset != target
+        } else {
+            self.state.offset = Point {
+                x: offset.x + remaining.x * SMOOTHING,
+                y: offset.y + remaining.y * SMOOTHING,
+            };
+            true
+        }
+    }
+
+    fn clamp(&self, offset: Point) -> Point {
+        let max = Point {
+            x: if self.horizontal { self.state.content.w - self.viewport.w } else { 0.0 },
+            y: if self.vertical { self.state.content.h - self.viewport.h } else { 0.0 },
+        };
+        Point {
+            x: offset.x.min(max.x).max(0.0),
+            y: offset.y.min(max.y).max(0.0),
+        }
+    }
+}
+
+/// The scroll offset per unit the thumb of a scrollbar is moved.
+pub(crate) fn drag_ratio(track: f64, thumb: f64, viewport: f64, content: f64) -> f64 {
+    if track > thumb {
+        (content - viewport).max(0.0) / (track - thumb)
+    } else {
+        0.0
+    }
+}
+
+/// The fraction of the remaining distance an animated scroll area moves per frame.
+const SMOOTHING: f64 = 0.35;
+
+/// The distance below which an animated scroll area jumps to its target.
+const SNAP_DISTANCE: f64 = 0.5;
+
+/// The smallest length of a scrollbar thumb, such that it can still be grabbed.
+const MIN_THUMB_LENGTH: f64 = 20.0;
+
+fn viewport(region: Rect, horizontal_bar: bool, vertical_bar: bool, bar_width: f64) -> Rect {
+    Rect {
+        w: if vertical_bar { (region.w - bar_width).max(0.0) } else { region.w },
+        h: if horizontal_bar { (region.h - bar_width).max(0.0) } else { region.h },
+        .. region
+    }
+}
+
+/// Compute position and length of a scrollbar thumb along its track.
+fn thumb(start: f64, track: f64, viewport: f64, content: f64, offset: f64) -> (f64, f64) {
+    let length = (track * viewport / content.max(1.0)).max(MIN_THUMB_LENGTH).min(track);
+    let ratio = drag_ratio(track, length, viewport, content);
+    let position = if ratio > 0.0 { offset / ratio } else { 0.0 };
+    (start + position, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A vertical scroll area of 100 by 100 units, whose scrollbar leaves a
+    /// viewport of 90 by 100 units for content that is 400 units high.
+    fn cursor() -> ScrollCursor {
+        let state = ScrollState { content: Size { w: 90.0, h: 400.0 }, .. ScrollState::default() };
+        ScrollCursor::new(Id::from_key("scroll"), &ScrollArea::new(), Rect { x: 0.0, y: 0.0, w: 100.0, h: 100.0 }, state, 10.0)
+    }
+
+    #[test]
+    fn offset_is_clamped_to_content() {
+        let mut scroll = cursor();
+        assert_eq!(scroll.viewport, Rect { x: 0.0, y: 0.0, w: 90.0, h: 100.0 });
+        assert!(scroll.vertical_bar && ! scroll.horizontal_bar);
+
+        scroll.set_offset(Point { x: 50.0, y: 500.0 });
+        assert_eq!(scroll.state.offset, Point { x: 0.0, y: 300.0 });
+        scroll.scroll_by(Point { x: 0.0, y: 120.0 });
+        assert_eq!(scroll.state.offset, Point { x: 0.0, y: 180.0 });
+        scroll.scroll_by(Point { x: 0.0, y: 500.0 });
+        assert_eq!(scroll.state.offset, Point::zero());
+    }
+
+    #[test]
+    fn scrollbar_thumb_follows_offset() {
+        let mut scroll = cursor();
+        let (track, thumb) = scroll.vertical_scrollbar(10.0).unwrap();
+        assert_eq!(track, Rect { x: 90.0, y: 0.0, w: 10.0, h: 100.0 });
+        assert_eq!(thumb, Rect { x: 90.0, y: 0.0, w: 10.0, h: 25.0 });
+
+        // Moving the thumb by one unit scrolls by the hidden content per free track unit
+        assert_eq!(drag_ratio(track.h, thumb.h, 100.0, 400.0), 4.0);
+        scroll.set_offset(Point { x: 0.0, y: 300.0 });
+        assert_eq!(scroll.vertical_scrollbar(10.0).unwrap().1.y, 75.0);
+        assert_eq!(drag_ratio(100.0, 100.0, 100.0, 50.0), 0.0);
+    }
+
+    #[test]
+    fn reveal_scrolls_minimal_distance() {
+        let mut scroll = cursor();
+        scroll.reveal(Rect { x: 0.0, y: 250.0, w: 90.0, h: 20.0 });
+        assert_eq!(scroll.state.target, Some(Point { x: 0.0, y: 170.0 }));
+
+        // Regions larger than the viewport are aligned to its start
+        scroll.reveal(Rect { x: 0.0, y: 150.0, w: 90.0, h: 200.0 });
+        assert_eq!(scroll.state.target, Some(Point { x: 0.0, y: 150.0 }));
+
+        let mut scroll = cursor();
+        scroll.reveal(Rect { x: 0.0, y: 50.0, w: 90.0, h: 20.0 });
+        assert_eq!(scroll.state.target, None);
+    }
+
+    #[test]
+    fn animation_converges_to_target() {
+        let mut scroll = cursor();
+        scroll.reveal(Rect { x: 0.0, y: 250.0, w: 90.0, h: 20.0 });
+
+        let mut frames = 0;
+        let mut previous = scroll.state.offset;
+        while scroll.animate() {
+            assert!(scroll.state.offset.y > previous.y);
+            previous = scroll.state.offset;
+            frames += 1;
+            assert!(frames < 30);
+        }
+        assert_eq!(scroll.state.offset, Point { x: 0.0, y: 170.0 });
+        assert_eq!(scroll.state.target, None);
+    }
+}
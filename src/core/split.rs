@@ -0,0 +1,319 @@
+use super::{Align, Id, Placement, Rect, SizeHint, Sizing};
+use super::layout::align_in;
+use serde::{Deserialize, Serialize};
+
+/// The direction in which the panes of a splitter are arranged.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Orientation {
+    /// Panes are placed side by side, separated by vertical dividers.
+    Horizontal,
+    /// Panes are placed on top of each other, separated by horizontal dividers.
+    Vertical,
+}
+
+/// Describes a container that divides its region into panes, whose sizes can
+/// be adjusted by dragging the dividers between them. Each child of the
+/// splitter fills one pane.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Splitter {
+    pub orientation: Orientation,
+    /// The initial share of each pane, which dividers are reset to by double clicking them.
+    pub ratios: Vec<f64>,
+    /// The smallest size of a pane along the orientation of the splitter.
+    pub min_size: f64,
+    /// The largest size of a pane along the orientation of the splitter.
+    pub max_size: f64,
+    /// The index, smallest and largest size of panes whose limits differ from
+    /// `min_size` and `max_size`.
+    pub pane_limits: Vec<(usize, f64, f64)>,
+    /// The placement of the splitter itself within its parent.
+    pub placement: Placement,
+}
+
+impl Splitter {
+    /// A splitter with the given number of equally sized panes.
+    pub fn new(orientation: Orientation, panes: usize) -> Self {
+        Splitter {
+            orientation,
+            ratios: vec![1.0 / panes.max(1) as f64; panes],
+            min_size: 20.0,
+            max_size: f64::INFINITY,
+            pane_limits: Vec::new(),
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn horizontal(panes: usize) -> Self {
+        Splitter::new(Orientation::Horizontal, panes)
+    }
+
+    pub fn vertical(panes: usize) -> Self {
+        Splitter::new(Orientation::Vertical, panes)
+    }
+
+    /// Set the initial shares of the panes, which are normalized to sum up to one.
+    pub fn ratios(self, ratios: Vec<f64>) -> Self {
+        let total: f64 = ratios.iter().sum();
+        let ratios = if total > 0.0 {
+            ratios.iter().map(|ratio| ratio / total).collect()
+        } else {
+            self.ratios
+        };
+        Splitter { ratios, .. self }
+    }
+
+    pub fn min_size(self, min_size: f64) -> Self {
+        Splitter { min_size, .. self }
+    }
+
+    pub fn max_size(self, max_size: f64) -> Self {
+        Splitter { max_size, .. self }
+    }
+
+    /// Limit the size of the pane with the given index, instead of using `min_size` and `max_size`.
+    pub fn pane_limits(mut self, index: usize, min_size: f64, max_size: f64) -> Self {
+        self.pane_limits.retain(|(other, _, _)| *other != index);
+        self.pane_limits.push((index, min_size, max_size));
+        self
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+
+    /// The extent of the region along the orientation of the splitter.
+    pub(crate) fn extent(&self, region: Rect) -> f64 {
+        match self.orientation {
+            Orientation::Horizontal => region.w,
+            Orientation::Vertical => region.h,
+        }
+    }
+
+    /// The size of each pane if the given extent is shared according to the ratios.
+    pub(crate) fn sizes(&self, ratios: &[f64], extent: f64) -> Vec<f64> {
+        let sizes = ratios.iter().map(|ratio| ratio * extent).collect();
+        let limits = self.limits(ratios.len(), extent);
+        limit_sizes(sizes, extent, &limits)
+    }
+
+    /// The smallest and largest size of each pane. If the extent is too small
+    /// for the smallest sizes, they are shrunk proportionally.
+    fn limits(&self, panes: usize, extent: f64) -> Vec<(f64, f64)> {
+        let mut limits: Vec<(f64, f64)> = (0 .. panes)
+            .map(|index| {
+                self.pane_limits.iter()
+                    .find(|(other, _, _)| *other == index)
+                    .map(|(_, min, max)| (*min, *max))
+                    .unwrap_or((self.min_size, self.max_size))
+            })
+            .collect();
+
+        let total_min: f64 = limits.iter().map(|(min, _)| min).sum();
+        if total_min > extent {
+            let scale = extent.max(0.0) / total_min;
+            for (min, _) in limits.iter_mut() {
+                *min *= scale;
+            }
+        }
+        for (min, max) in limits.iter_mut() {
+            *max = max.max(*min);
+        }
+
+        limits
+    }
+
+    /// Rectangles of the panes and the dividers between them.
+    pub(crate) fn rects(&self, region: Rect, sizes: &[f64], divider: f64) -> (Vec<Rect>, Vec<Rect>) {
+        let mut panes = Vec::new();
+        let mut dividers = Vec::new();
+        let mut offset = 0.0;
+
+        for (index, size) in sizes.iter().enumerate() {
+            if index > 0 {
+                dividers.push(self.slice(region, offset, divider));
+                offset += divider;
+            }
+            panes.push(self.slice(region, offset, *size));
+            offset += size;
+        }
+
+        (panes, dividers)
+    }
+
+    fn slice(&self, region: Rect, offset: f64, size: f64) -> Rect {
+        match self.orientation {
+            Orientation::Horizontal => Rect { x: region.x + offset, w: size, .. region },
+            Orientation::Vertical => Rect { y: region.y + offset, h: size, .. region },
+        }
+    }
+}
+
+/// The state of a splitter that is kept across frames.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct SplitState {
+    /// The share of each pane in the extent available to the panes.
+    pub ratios: Vec<f64>,
+    /// The mouse position along the orientation and the pane sizes when dragging a divider started.
+    pub drag: Option<(f64, Vec<f64>)>,
+}
+
+impl SplitState {
+    pub fn new(splitter: &Splitter) -> Self {
+        SplitState {
+            ratios: splitter.ratios.clone(),
+            drag: None,
+        }
+    }
+
+    /// Move the divider after the pane with the given index by the distance
+    /// the mouse was dragged, starting from the given pane sizes.
+    pub fn drag(&mut self, splitter: &Splitter, divider: usize, sizes: &[f64], distance: f64) {
+        let mut sizes = sizes.to_vec();
+        let limits = splitter.limits(sizes.len(), sizes.iter().sum());
+        let ((min_before, max_before), (min_after, max_after)) = (limits[divider], limits[divider + 1]);
+        let total = sizes[divider] + sizes[divider + 1];
+        let lower = min_before.max(total - max_after);
+        let upper = max_before.min(total - min_after);
+
+        let before = (sizes[divider] + distance).min(upper).max(lower);
+        sizes[divider] = before;
+        sizes[divider + 1] = total - before;
+        self.set_sizes(&sizes);
+    }
+
+    fn set_sizes(&mut self, sizes: &[f64]) {
+        let extent: f64 = sizes.iter().sum();
+        if extent > 0.0 {
+            self.ratios = sizes.iter().map(|size| size / extent).collect();
+        }
+    }
+}
+
+/// Restrict the sizes to the given ranges, while keeping their sum unchanged
+/// where possible by growing or shrinking the other panes.
+fn limit_sizes(mut sizes: Vec<f64>, extent: f64, limits: &[(f64, f64)]) -> Vec<f64> {
+    for (size, (min, max)) in sizes.iter_mut().zip(limits) {
+        *size = size.min(*max).max(*min);
+    }
+
+    let mut excess: f64 = sizes.iter().sum::<f64>() - extent;
+    for (size, (min, max)) in sizes.iter_mut().zip(limits).rev() {
+        let limited = (*size - excess).min(*max).max(*min);
+        excess -= *size - limited;
+        *size = limited;
+    }
+
+    sizes
+}
+
+/// The state of a splitter while its children are being created.
+pub(crate) struct SplitCursor {
+    id: Id,
+    panes: Vec<Rect>,
+    children: usize,
+}
+
+impl SplitCursor {
+    pub fn new(id: Id, panes: Vec<Rect>) -> Self {
+        SplitCursor {
+            id,
+            panes,
+            children: 0,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn children(&self) -> usize {
+        self.children
+    }
+
+    /// The pane of the next child, or an empty region after all panes have been used.
+    pub fn available(&self) -> Rect {
+        self.panes.get(self.children).cloned().unwrap_or_else(|| {
+            let last = self.panes.last().cloned().unwrap_or_else(Rect::zero);
+            Rect { w: 0.0, h: 0.0, .. last }
+        })
+    }
+
+    pub fn allocate(&mut self, width: Sizing, height: Sizing, hint: SizeHint) -> Rect {
+        let pane = self.available();
+        self.children += 1;
+
+        let (x, w) = align_in(width, hint.horizontal(), pane.x, pane.w, Align::Stretch);
+        let (y, h) = align_in(height, hint.vertical(), pane.y, pane.h, Align::Stretch);
+        Rect { x, y, w, h }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_sizes_keeps_sum() {
+        // The first pane is grown to its minimum at the expense of the last one
+        let limits = [(50.0, 100.0), (0.0, 1000.0), (0.0, 1000.0)];
+        assert_eq!(limit_sizes(vec![20.0, 80.0, 100.0], 200.0, &limits), vec![50.0, 80.0, 70.0]);
+
+        // If the last pane reaches its limit, the panes before it are adjusted
+        let limits = [(0.0, 1000.0), (0.0, 1000.0), (0.0, 60.0)];
+        assert_eq!(limit_sizes(vec![40.0, 60.0, 100.0], 200.0, &limits), vec![40.0, 100.0, 60.0]);
+    }
+
+    #[test]
+    fn limit_sizes_exceeds_extent_if_unavoidable() {
+        let limits = [(80.0, 100.0), (80.0, 100.0)];
+        assert_eq!(limit_sizes(vec![50.0, 50.0], 100.0, &limits), vec![80.0, 80.0]);
+    }
+
+    #[test]
+    fn pane_limits_override_defaults() {
+        let splitter = Splitter::horizontal(3).min_size(10.0).max_size(200.0).pane_limits(1, 120.0, 150.0);
+        assert_eq!(splitter.sizes(&[0.25, 0.25, 0.5], 400.0), vec![100.0, 120.0, 180.0]);
+
+        // The smallest sizes shrink proportionally if the extent is too small
+        assert_eq!(splitter.sizes(&[0.25, 0.25, 0.5], 70.0), vec![5.0, 60.0, 5.0]);
+    }
+
+    #[test]
+    fn drag_respects_limits_of_both_panes() {
+        let splitter = Splitter::horizontal(2).min_size(30.0).pane_limits(1, 50.0, 150.0);
+        let mut state = SplitState::new(&splitter);
+        let sizes = [100.0, 100.0];
+
+        state.drag(&splitter, 0, &sizes, 20.0);
+        assert_eq!(state.ratios, vec![0.6, 0.4]);
+        state.drag(&splitter, 0, &sizes, 80.0);
+        assert_eq!(state.ratios, vec![0.75, 0.25]);
+        state.drag(&splitter, 0, &sizes, -80.0);
+        assert_eq!(state.ratios, vec![0.25, 0.75]);
+    }
+}
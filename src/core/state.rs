@@ -1,16 +1,46 @@
-use super::{Point, Id};
+use super::{Point, Size, Rect, Id, Layer, InputEvent, KeyEvent, ScrollDelta, PointerButton};
 use std::collections::VecDeque;
 
-pub use glutin::{VirtualKeyCode, ModifiersState};
-
 /// Internal state of the UI.
 pub struct GluiState {
     // MOUSE STATE
     pub mouse_pos: Point,
-    pub mouse_left: ButtonState,
+    /// The mouse buttons that are currently held down.
+    mouse_down: Vec<PointerButton>,
+    /// The mouse buttons that were pressed since the previous frame.
+    mouse_pressed: Vec<PointerButton>,
+    /// The mouse buttons that were released since the previous frame.
+    mouse_released: Vec<PointerButton>,
+    /// The distance scrolled since the last frame that was not consumed by a
+    /// scroll area, in logical units.
+    pub scroll_delta: Point,
+    /// The distance scrolled since the last frame by devices that scroll in
+    /// lines, like most mouse wheels, in lines.
+    pub scroll_lines: Point,
+    /// The distance scrolled since the last frame by devices that scroll in
+    /// pixels, like trackpads, in logical units.
+    pub scroll_pixels: Point,
+    /// The logical distance a scroll area moves for each line scrolled.
+    pub scroll_line_height: f64,
+    /// The number of times the most recently pressed mouse button was pressed
+    /// in quick succession at about the same position, e.g. 2 for a double click.
+    pub click_count: u32,
+    /// The longest time in seconds between two presses that are counted as successive clicks.
+    pub double_click_interval: f64,
+    /// The button, time and position of the last press of a mouse button.
+    last_press: Option<(PointerButton, f64, Point)>,
+    /// The position where the left mouse button was pressed, while it is held down.
+    press_pos: Option<Point>,
+    /// The mouse position when the previous frame was finished.
+    previous_mouse_pos: Point,
+
+    /// Seconds since the start of the session, as last reported by the host.
+    pub time: f64,
 
     // WINDOW STATE
     pub window_close: bool,
+    /// The logical size of the area the UI is drawn into.
+    pub window_size: Size,
 
     // KEYBOARD STATE
     pub text_input: String,
@@ -22,47 +52,116 @@ pub struct GluiState {
     pub hover_widget: Option<Id>,
     /// The ID of the widget where the mouse button was pressed, if any.
     pub active_widget: Option<Id>,
+    /// The widgets where the other mouse buttons were pressed, while they are held down.
+    press_targets: Vec<(PointerButton, Id)>,
 
     pub focus_widget: Option<Id>,
+    /// Whether the focused widget should be scrolled into view, because the
+    /// focus was moved with the keyboard or programmatically.
+    pub reveal_focus: bool,
 
     pub last_focusable_widget: Option<Id>,
+    /// The widget that had the focus at the end of the previous frame.
+    previous_focus: Option<Id>,
+
+    /// The regions of the interactive widgets created in the current layout pass, in creation order.
+    hit_regions: Vec<HitRegion>,
+    /// The regions of the interactive widgets created in the last pass of the
+    /// previous frame, which decide the widget that receives mouse input.
+    previous_hit_regions: Vec<HitRegion>,
+    /// The modals shown in the current frame, in the order they were opened.
+    modals: Vec<Id>,
+    /// The modals shown in the previous frame, of which the last one traps the keyboard focus.
+    previous_modals: Vec<Id>,
+}
+
+/// The input of a frame that is held back from layout passes, see `GluiState::hold_input`.
+pub(crate) struct HeldInput {
+    mouse_pressed: Vec<PointerButton>,
+    mouse_released: Vec<PointerButton>,
+    key_input: VecDeque<KeyEvent>,
+    text_input: String,
+    scroll_delta: Point,
+    scroll_lines: Point,
+    scroll_pixels: Point,
+    previous_mouse_pos: Point,
+    reveal_focus: bool,
+}
+
+/// A region recorded while building a frame that receives or blocks mouse input.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HitRegion {
+    /// The widget the region belongs to, or `None` for regions that only
+    /// hide the widgets below them from the mouse.
+    pub widget: Option<Id>,
+    pub layer: Layer,
+    /// The position from the bottom of the window containing the region,
+    /// starting at 1, or 0 outside of windows.
+    pub depth: usize,
+    pub region: Rect,
+}
+
+impl Default for GluiState {
+    fn default() -> Self {
+        GluiState::new()
+    }
 }
 
 impl GluiState {
     pub fn new() -> Self {
         GluiState {
             mouse_pos: Point { x: 0.0, y: 0.0 },
-            mouse_left: ButtonState::Released,
+            mouse_down: Vec::new(),
+            mouse_pressed: Vec::new(),
+            mouse_released: Vec::new(),
+            scroll_delta: Point::zero(),
+            scroll_lines: Point::zero(),
+            scroll_pixels: Point::zero(),
+            scroll_line_height: 20.0,
+            click_count: 0,
+            double_click_interval: 0.5,
+            last_press: None,
+            press_pos: None,
+            previous_mouse_pos: Point::zero(),
+            time: 0.0,
             window_close: false,
+            window_size: Size::zero(),
             text_input: String::new(),
             key_input: VecDeque::new(),
             hover_widget: None,
             active_widget: None,
+            press_targets: Vec::new(),
             focus_widget: None,
+            reveal_focus: false,
             last_focusable_widget: None,
+            previous_focus: None,
+            hit_regions: Vec::new(),
+            previous_hit_regions: Vec::new(),
+            modals: Vec::new(),
+            previous_modals: Vec::new(),
         }
     }
 
-    /// Should be called before starting to build the current frame in order
-    /// to correctly set up the internal state.
+    /// Should be called before each layout pass of a frame in order to
+    /// discard what the previous pass recorded.
     pub fn begin(&mut self) {
         self.hover_widget = None;
+        self.hit_regions.clear();
+        self.modals.clear();
     }
 
-    /// Should be called after building a frame in order to reset the state
-    /// to make the next iteration work properly.
+    /// Should be called once after building a frame in order to reset the
+    /// input that arrived since the previous frame.
     pub fn end(&mut self) {
-        if self.mouse_left.is_pressed() {
-            // If no item became active after pressing the left mouse button,
-            // we set the active item to an invalid ID in order to prevent any
-            // other item from becoming active just by moving the mouse above it.
-            if self.active_widget.is_none() {
-                self.active_widget = Some(Id::invalid());
-            }
-        } else {
-            // If the left mouse button isn't held down, nothing is active.
+        // Widgets only become active when the left mouse button is pressed
+        // above them, and stay active until it is released. The same holds
+        // for the targets of the other buttons.
+        if ! self.is_mouse_down(PointerButton::Left) {
             self.active_widget = None;
+            self.press_pos = None;
         }
+        let mouse_down = &self.mouse_down;
+        self.press_targets.retain(|(button, _)| mouse_down.contains(button));
 
         // Keyboard input was not consumed by a widget, assume focused element
         // has been destroyed.
@@ -71,8 +170,230 @@ impl GluiState {
             self.key_input.clear();
         }
 
+        self.previous_focus = self.focus_widget;
+
         // Clear any text input that wasn't consumed
         self.text_input.clear();
+        self.scroll_delta = Point::zero();
+        self.scroll_lines = Point::zero();
+        self.scroll_pixels = Point::zero();
+        self.mouse_pressed.clear();
+        self.mouse_released.clear();
+        self.previous_mouse_pos = self.mouse_pos;
+
+        self.previous_hit_regions = std::mem::take(&mut self.hit_regions);
+        self.previous_modals = std::mem::take(&mut self.modals);
+    }
+
+    /// Take the input that arrived since the previous frame out of the state,
+    /// such that layout passes can be run without reacting to it. Returns
+    /// `None` if there is no such input.
+    pub(crate) fn hold_input(&mut self) -> Option<HeldInput> {
+        let pending = ! self.mouse_pressed.is_empty()
+            || ! self.mouse_released.is_empty()
+            || ! self.key_input.is_empty()
+            || ! self.text_input.is_empty()
+            || self.scroll_lines != Point::zero()
+            || self.scroll_pixels != Point::zero()
+            || self.drag_delta() != Point::zero()
+            || self.reveal_focus;
+        if ! pending {
+            return None;
+        }
+
+        Some(HeldInput {
+            mouse_pressed: std::mem::take(&mut self.mouse_pressed),
+            mouse_released: std::mem::take(&mut self.mouse_released),
+            key_input: std::mem::take(&mut self.key_input),
+            text_input: std::mem::take(&mut self.text_input),
+            scroll_delta: std::mem::replace(&mut self.scroll_delta, Point::zero()),
+            scroll_lines: std::mem::replace(&mut self.scroll_lines, Point::zero()),
+            scroll_pixels: std::mem::replace(&mut self.scroll_pixels, Point::zero()),
+            previous_mouse_pos: std::mem::replace(&mut self.previous_mouse_pos, self.mouse_pos),
+            reveal_focus: std::mem::replace(&mut self.reveal_focus, false),
+        })
+    }
+
+    /// Put back the input taken by `hold_input`.
+    pub(crate) fn release_input(&mut self, input: HeldInput) {
+        self.mouse_pressed = input.mouse_pressed;
+        self.mouse_released = input.mouse_released;
+        self.key_input = input.key_input;
+        self.text_input = input.text_input;
+        self.scroll_delta = input.scroll_delta;
+        self.scroll_lines = input.scroll_lines;
+        self.scroll_pixels = input.scroll_pixels;
+        self.previous_mouse_pos = input.previous_mouse_pos;
+        self.reveal_focus = input.reveal_focus;
+    }
+
+    /// Record a region that receives or blocks mouse input, see `hit_at`.
+    pub fn add_hit_region(&mut self, hit: HitRegion) {
+        self.hit_regions.push(hit);
+    }
+
+    /// The topmost region at the given point in the previous frame. Regions in
+    /// higher layers are above those in lower layers, then regions in windows
+    /// closer to the top, then regions that were recorded later.
+    pub fn hit_at(&self, point: Point) -> Option<HitRegion> {
+        self.previous_hit_regions.iter()
+            .enumerate()
+            .filter(|(_, hit)| hit.region.contains(point))
+            .max_by_key(|(index, hit)| (hit.layer, hit.depth, *index))
+            .map(|(_, hit)| *hit)
+    }
+
+    /// Record that a modal was shown in the current frame.
+    pub fn add_modal(&mut self, id: Id) {
+        self.modals.push(id);
+    }
+
+    /// Whether the modal was shown in the previous frame.
+    pub fn was_modal_shown(&self, id: Id) -> bool {
+        self.previous_modals.contains(&id)
+    }
+
+    /// The modal opened last in the previous frame, outside of which no widget
+    /// can take the keyboard focus.
+    pub fn top_modal(&self) -> Option<Id> {
+        self.previous_modals.last().cloned()
+    }
+
+    /// Update the state according to an input event received by the host.
+    pub fn apply(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::MouseMove(pos) => self.mouse_pos = *pos,
+            InputEvent::PointerButton(button, state) => {
+                let down = self.is_mouse_down(*button);
+                if state.is_pressed() && ! down {
+                    self.count_click(*button);
+                    self.mouse_down.push(*button);
+                    self.mouse_pressed.push(*button);
+                    if *button == PointerButton::Left {
+                        self.press_pos = Some(self.mouse_pos);
+                    }
+                } else if ! state.is_pressed() && down {
+                    self.mouse_down.retain(|other| other != button);
+                    self.mouse_released.push(*button);
+                }
+            }
+            InputEvent::Scroll(ScrollDelta::Lines(lines)) => {
+                self.scroll_lines.x += lines.x;
+                self.scroll_lines.y += lines.y;
+                self.scroll_delta.x += lines.x * self.scroll_line_height;
+                self.scroll_delta.y += lines.y * self.scroll_line_height;
+            }
+            InputEvent::Scroll(ScrollDelta::Pixels(pixels)) => {
+                self.scroll_pixels.x += pixels.x;
+                self.scroll_pixels.y += pixels.y;
+                self.scroll_delta.x += pixels.x;
+                self.scroll_delta.y += pixels.y;
+            }
+            InputEvent::Key(key_event) => self.key_input.push_back(key_event.clone()),
+            InputEvent::Text(ch) => self.text_input.push(*ch),
+            InputEvent::CloseRequested => self.window_close = true,
+            InputEvent::Resized(size) => self.window_size = *size,
+            InputEvent::Time(time) => self.time = *time,
+        }
+    }
+
+    fn count_click(&mut self, button: PointerButton) {
+        let (time, pos) = (self.time, self.mouse_pos);
+        let successive = self.last_press.is_some_and(|(last_button, last_time, last_pos)| {
+            last_button == button
+                && time - last_time <= self.double_click_interval
+                && (pos.x - last_pos.x).abs() <= CLICK_DISTANCE
+                && (pos.y - last_pos.y).abs() <= CLICK_DISTANCE
+        });
+
+        self.click_count = if successive { self.click_count + 1 } else { 1 };
+        self.last_press = Some((button, time, pos));
+    }
+
+    /// Whether the mouse button is currently held down.
+    pub fn is_mouse_down(&self, button: PointerButton) -> bool {
+        self.mouse_down.contains(&button)
+    }
+
+    /// Whether the mouse button was pressed since the previous frame.
+    pub fn mouse_pressed(&self, button: PointerButton) -> bool {
+        self.mouse_pressed.contains(&button)
+    }
+
+    /// Whether the mouse button was released since the previous frame.
+    pub fn mouse_released(&self, button: PointerButton) -> bool {
+        self.mouse_released.contains(&button)
+    }
+
+    /// The number of successive clicks of the button, see `click_count`,
+    /// or 0 if another button was pressed more recently.
+    pub fn clicks(&self, button: PointerButton) -> u32 {
+        match self.last_press {
+            Some((last_button, _, _)) if last_button == button => self.click_count,
+            _ => 0,
+        }
+    }
+
+    /// The widget where the mouse button was pressed, while it is held down.
+    /// For the left button, this is the active widget.
+    pub fn press_target(&self, button: PointerButton) -> Option<Id> {
+        match button {
+            PointerButton::Left => self.active_widget,
+            _ => self.press_targets.iter().find(|(other, _)| *other == button).map(|(_, id)| *id),
+        }
+    }
+
+    /// Record the widget where the mouse button was pressed, unless the
+    /// button was already pressed above another widget.
+    pub fn set_press_target(&mut self, button: PointerButton, widget: Id) {
+        match button {
+            PointerButton::Left => {
+                if self.active_widget.is_none() {
+                    self.active_widget = Some(widget);
+                }
+            }
+            _ => {
+                if self.press_target(button).is_none() {
+                    self.press_targets.push((button, widget));
+                }
+            }
+        }
+    }
+
+    /// Whether the left mouse button is held down on the active widget, in
+    /// which case moving the mouse drags the widget.
+    pub fn is_dragging(&self) -> bool {
+        self.active_widget.is_some() && self.is_mouse_down(PointerButton::Left)
+    }
+
+    /// The position where the mouse was pressed on the active widget, while it is dragged.
+    pub fn drag_start(&self) -> Option<Point> {
+        self.press_pos.filter(|_| self.is_dragging())
+    }
+
+    /// The distance the mouse moved since the previous frame while dragging the
+    /// active widget, or since the press if the button was pressed in this frame.
+    pub fn drag_delta(&self) -> Point {
+        if self.is_dragging() {
+            let previous = match self.press_pos {
+                Some(press_pos) if self.mouse_pressed(PointerButton::Left) => press_pos,
+                _ => self.previous_mouse_pos,
+            };
+            Point {
+                x: self.mouse_pos.x - previous.x,
+                y: self.mouse_pos.y - previous.y,
+            }
+        } else {
+            Point::zero()
+        }
+    }
+
+    /// The distance the mouse moved since dragging the active widget started.
+    pub fn drag_total(&self) -> Point {
+        match self.drag_start() {
+            Some(start) => Point { x: self.mouse_pos.x - start.x, y: self.mouse_pos.y - start.y },
+            None => Point::zero(),
+        }
     }
 
     pub fn is_hover(&self, widget: Id) -> bool {
@@ -86,43 +407,100 @@ impl GluiState {
     pub fn has_focus(&self, widget: Id) -> bool {
         self.focus_widget == Some(widget)
     }
-}
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-pub enum ButtonState {
-    Pressed,
-    Released,
-}
+    /// Whether the widget had the focus at the end of the previous frame.
+    pub fn had_focus(&self, widget: Id) -> bool {
+        self.previous_focus == Some(widget)
+    }
 
-impl ButtonState {
-    pub fn is_pressed(&self) -> bool {
-        *self == ButtonState::Pressed
+    /// Move the keyboard focus to the given widget and scroll it into view.
+    pub fn request_focus(&mut self, widget: Id) {
+        self.focus_widget = Some(widget);
+        self.reveal_focus = true;
     }
 }
 
-impl From<glutin::ElementState> for ButtonState {
-    fn from(other: glutin::ElementState) -> ButtonState {
-        match other {
-            glutin::ElementState::Pressed => ButtonState::Pressed,
-            glutin::ElementState::Released => ButtonState::Released,
-        }
+/// The farthest the mouse may move between two presses that are counted as successive clicks.
+const CLICK_DISTANCE: f64 = 4.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ButtonState;
+
+    /// Move the mouse to the position at the given time and click the button.
+    fn click(state: &mut GluiState, button: PointerButton, x: f64, y: f64, time: f64) -> u32 {
+        state.apply(&InputEvent::Time(time));
+        state.apply(&InputEvent::MouseMove(Point { x, y }));
+        state.apply(&InputEvent::PointerButton(button, ButtonState::Pressed));
+        state.apply(&InputEvent::PointerButton(button, ButtonState::Released));
+        state.end();
+        state.clicks(button)
     }
-}
 
-pub struct KeyEvent {
-    pub key: VirtualKeyCode,
-    pub modifiers: ModifiersState,
-    pub state: ButtonState,
-}
+    #[test]
+    fn successive_clicks_are_counted() {
+        let mut state = GluiState::new();
+        let left = PointerButton::Left;
+        assert_eq!(click(&mut state, left, 10.0, 10.0, 1.0), 1);
+        assert_eq!(click(&mut state, left, 12.0, 7.0, 1.3), 2);
+        assert_eq!(click(&mut state, left, 12.0, 7.0, 1.7), 3);
 
-impl std::convert::TryFrom<glutin::KeyboardInput> for KeyEvent {
-    type Error = ();
+        // Too late, too far away or with another button in between
+        assert_eq!(click(&mut state, left, 12.0, 7.0, 2.3), 1);
+        assert_eq!(click(&mut state, left, 17.0, 7.0, 2.4), 1);
+        assert_eq!(click(&mut state, PointerButton::Right, 17.0, 7.0, 2.5), 1);
+        assert_eq!(state.clicks(left), 0);
+        assert_eq!(click(&mut state, left, 17.0, 7.0, 2.6), 1);
+    }
 
-    fn try_from(other: glutin::KeyboardInput) -> Result<KeyEvent, ()> {
-        Ok(KeyEvent {
-            key: other.virtual_keycode.ok_or(())?,
-            modifiers: other.modifiers,
-            state: other.state.into(),
-        })
+    #[test]
+    fn drag_of_active_widget() {
+        let mut state = GluiState::new();
+        state.apply(&InputEvent::MouseMove(Point { x: 10.0, y: 10.0 }));
+        state.end();
+
+        // Moving the mouse before pressing the button in the same frame is no drag
+        state.apply(&InputEvent::MouseMove(Point { x: 20.0, y: 30.0 }));
+        state.apply(&InputEvent::PointerButton(PointerButton::Left, ButtonState::Pressed));
+        state.active_widget = Some(Id::from_key("widget"));
+        assert_eq!(state.drag_start(), Some(Point { x: 20.0, y: 30.0 }));
+        assert_eq!(state.drag_delta(), Point::zero());
+        state.end();
+
+        state.apply(&InputEvent::MouseMove(Point { x: 25.0, y: 28.0 }));
+        state.apply(&InputEvent::MouseMove(Point { x: 30.0, y: 25.0 }));
+        assert_eq!(state.drag_delta(), Point { x: 10.0, y: -5.0 });
+        assert_eq!(state.drag_total(), Point { x: 10.0, y: -5.0 });
+        state.end();
+
+        state.apply(&InputEvent::MouseMove(Point { x: 35.0, y: 25.0 }));
+        assert_eq!(state.drag_delta(), Point { x: 5.0, y: 0.0 });
+        assert_eq!(state.drag_total(), Point { x: 15.0, y: -5.0 });
+
+        // Releasing the button ends the drag
+        state.apply(&InputEvent::PointerButton(PointerButton::Left, ButtonState::Released));
+        state.end();
+        assert_eq!(state.active_widget, None);
+        assert_eq!(state.drag_start(), None);
+        assert_eq!(state.drag_total(), Point::zero());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn scrolling_accumulates_per_device() {
+        let mut state = GluiState::new();
+        state.scroll_line_height = 15.0;
+        state.apply(&InputEvent::Scroll(ScrollDelta::Lines(Point { x: 0.0, y: -1.0 })));
+        state.apply(&InputEvent::Scroll(ScrollDelta::Lines(Point { x: 1.0, y: -2.0 })));
+        state.apply(&InputEvent::Scroll(ScrollDelta::Pixels(Point { x: 4.0, y: 10.0 })));
+
+        assert_eq!(state.scroll_lines, Point { x: 1.0, y: -3.0 });
+        assert_eq!(state.scroll_pixels, Point { x: 4.0, y: 10.0 });
+        assert_eq!(state.scroll_delta, Point { x: 19.0, y: -35.0 });
+
+        state.end();
+        assert_eq!(state.scroll_lines, Point::zero());
+        assert_eq!(state.scroll_pixels, Point::zero());
+        assert_eq!(state.scroll_delta, Point::zero());
+    }
+}
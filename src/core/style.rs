@@ -1,4 +1,33 @@
-pub use nanovg::Color;
+use super::Font;
+use serde::{Deserialize, Serialize};
+
+/// An RGBA color with components in the range `0.0..=1.0`.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color { r, g, b, a }
+    }
+
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Color::from_rgba(r, g, b, 255)
+    }
+
+    pub fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color {
+            r: f32::from(r) / 255.0,
+            g: f32::from(g) / 255.0,
+            b: f32::from(b) / 255.0,
+            a: f32::from(a) / 255.0,
+        }
+    }
+}
 
 pub struct Style {
     pub font_size: f32,
@@ -11,6 +40,50 @@ pub struct Style {
     pub clickable_active: Color,
     pub clickable_normal: Color,
     pub widget_border: BorderStyle,
+    /// Space between the border of a widget and its content.
+    pub widget_padding: f64,
+    /// The width below which text boxes are not shrunk, even if their content is shorter.
+    pub text_box_min_width: f64,
+
+    pub scrollbar_width: f64,
+    pub scrollbar_track: Color,
+    pub scrollbar_thumb: Color,
+    /// The color of a scrollbar thumb that is hovered or dragged.
+    pub scrollbar_thumb_active: Color,
+
+    /// The thickness of the dividers between the panes of a splitter.
+    pub splitter_width: f64,
+    pub splitter_color: Color,
+    /// The color of a divider that is hovered or dragged.
+    pub splitter_active: Color,
+
+    pub window_background: Color,
+    pub window_title_height: f64,
+    pub window_title: Color,
+    /// The color of the title bar of the topmost window.
+    pub window_title_active: Color,
+    /// The width of the border along which windows can be resized.
+    pub window_resize_border: f64,
+
+    /// The height of the tabs above docked panels.
+    pub tab_height: f64,
+    /// The color highlighting where a dragged panel is docked when dropped.
+    pub dock_preview: Color,
+
+    /// The color covering the UI below an open modal.
+    pub modal_dim: Color,
+    /// The smallest width of a dialog, such that short messages do not look cramped.
+    pub dialog_min_width: f64,
+}
+
+impl Style {
+    /// The font used for widget labels and text input.
+    pub fn font(&self) -> Font<'_> {
+        Font {
+            name: &self.font_name,
+            size: self.font_size,
+        }
+    }
 }
 
 impl Default for Style {
@@ -24,6 +97,24 @@ impl Default for Style {
             clickable_hover: Color::from_rgb(240, 240, 240),
             clickable_normal: Color::from_rgb(255, 255, 255),
             widget_border: BorderStyle::default(),
+            widget_padding: 5.0,
+            text_box_min_width: 100.0,
+            scrollbar_width: 10.0,
+            scrollbar_track: Color::from_rgb(240, 240, 240),
+            scrollbar_thumb: Color::from_rgb(190, 190, 190),
+            scrollbar_thumb_active: Color::from_rgb(140, 140, 140),
+            splitter_width: 6.0,
+            splitter_color: Color::from_rgb(225, 225, 225),
+            splitter_active: Color::from_rgb(160, 160, 160),
+            window_background: Color::from_rgb(250, 250, 250),
+            window_title_height: 24.0,
+            window_title: Color::from_rgb(215, 215, 215),
+            window_title_active: Color::from_rgb(175, 195, 225),
+            window_resize_border: 5.0,
+            tab_height: 24.0,
+            dock_preview: Color::from_rgba(70, 130, 220, 80),
+            modal_dim: Color::from_rgba(0, 0, 0, 90),
+            dialog_min_width: 240.0,
         }
     }
 }
@@ -40,4 +131,4 @@ impl Default for BorderStyle {
             radius: 3.0
         }
     }
-}
\ No newline at end of file
+}
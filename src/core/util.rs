@@ -1,4 +1,6 @@
-#[derive(Copy, Clone, PartialEq, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -14,7 +16,7 @@ impl Point {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Size {
     pub w: f64,
     pub h: f64,
@@ -25,6 +27,10 @@ impl Size {
         Size { w: 0.0, h: 0.0 }
     }
 
+    pub fn as_f32(&self) -> (f32, f32) {
+        (self.w as f32, self.h as f32)
+    }
+
     pub fn smallest(&self, other: &Size) -> Size {
         Size {
             w: self.w.min(other.w),
@@ -33,7 +39,7 @@ impl Size {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Rect {
     pub x: f64,
     pub y: f64,
@@ -98,6 +104,21 @@ impl Rect {
         }
     }
 
+    /// Returns the overlapping part of both rectangles, which is empty if they are disjoint.
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = (self.x + self.w).min(other.x + other.w);
+        let bottom = (self.y + self.h).min(other.y + other.h);
+
+        Rect {
+            x,
+            y,
+            w: (right - x).max(0.0),
+            h: (bottom - y).max(0.0),
+        }
+    }
+
     pub fn contains(&self, point: Point) -> bool {
         point.x >= self.x && point.x < self.x + self.w
         && point.y >= self.y && point.y < self.y + self.h
@@ -0,0 +1,8 @@
+use super::{GluiFrame, Renderer, Response};
+
+/// A piece of UI that places, handles and draws itself within a frame, see
+/// `GluiFrame::add`. Widgets that need to remember something across frames
+/// can keep it in the frame with `GluiFrame::state`.
+pub trait Widget {
+    fn ui<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response;
+}
@@ -0,0 +1,95 @@
+use super::Id;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// The state of widgets that is kept across frames, stored by widget ID and type.
+/// Entries that are not used for a number of frames are dropped, see `Glui::state_lifetime`.
+#[derive(Default)]
+pub(crate) struct WidgetStates {
+    entries: HashMap<(Id, TypeId), Entry>,
+    /// The number of frames finished so far.
+    frame: u64,
+}
+
+struct Entry {
+    value: Box<dyn Any>,
+    /// The frame in which the entry was used last.
+    last_used: u64,
+}
+
+impl WidgetStates {
+    /// The state of the given type stored for the widget, which is kept for
+    /// another lifetime.
+    pub fn get<T: 'static>(&mut self, id: Id) -> Option<&mut T> {
+        let frame = self.frame;
+        self.entries.get_mut(&(id, TypeId::of::<T>())).and_then(|entry| {
+            entry.last_used = frame;
+            entry.value.downcast_mut()
+        })
+    }
+
+    /// The state of the given type stored for the widget, without affecting its lifetime.
+    pub fn peek<T: 'static>(&self, id: Id) -> Option<&T> {
+        self.entries.get(&(id, TypeId::of::<T>())).and_then(|entry| entry.value.downcast_ref())
+    }
+
+    pub fn insert<T: 'static>(&mut self, id: Id, value: T) {
+        let entry = Entry {
+            value: Box::new(value),
+            last_used: self.frame,
+        };
+        self.entries.insert((id, TypeId::of::<T>()), entry);
+    }
+
+    pub fn remove<T: 'static>(&mut self, id: Id) -> Option<T> {
+        self.entries.remove(&(id, TypeId::of::<T>()))
+            .and_then(|entry| entry.value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// Finish a frame and drop all entries that were not used in the given number of frames.
+    pub fn end_frame(&mut self, lifetime: u64) {
+        let frame = self.frame;
+        self.entries.retain(|_, entry| frame - entry.last_used < lifetime);
+        self.frame += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_state_is_dropped_after_lifetime() {
+        let mut states = WidgetStates::default();
+        let (used, unused) = (Id::from_key("used"), Id::from_key("unused"));
+        states.insert(used, 1u32);
+        states.insert(unused, 2u32);
+
+        for _ in 0 .. 3 {
+            states.end_frame(3);
+            assert_eq!(states.get::<u32>(used), Some(&mut 1));
+            assert_eq!(states.peek::<u32>(unused), Some(&2));
+        }
+        // Peeking does not keep the state alive
+        states.end_frame(3);
+        assert_eq!(states.peek::<u32>(used), Some(&1));
+        assert_eq!(states.peek::<u32>(unused), None);
+    }
+
+    #[test]
+    fn states_of_different_types_are_separate() {
+        let mut states = WidgetStates::default();
+        let id = Id::from_key("widget");
+        states.insert(id, 1u32);
+        states.insert(id, "text");
+        states.insert(Id::from_key("other"), 3u32);
+
+        assert_eq!(states.peek::<u32>(id), Some(&1));
+        assert_eq!(states.peek::<&str>(id), Some(&"text"));
+        assert_eq!(states.peek::<u64>(id), None);
+        assert_eq!(states.remove::<u32>(id), Some(1));
+        assert_eq!(states.peek::<u32>(id), None);
+        assert_eq!(states.peek::<&str>(id), Some(&"text"));
+    }
+}
@@ -0,0 +1,218 @@
+use super::{DisplayList, Id, Layer, Layout, Point, Rect, Size};
+use super::scroll::ScrollCursor;
+use super::widget_state::WidgetStates;
+
+/// Describes a movable window that floats above the rest of the UI, with a
+/// title bar for dragging it around and handles along its border for resizing it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Window {
+    pub title: String,
+    /// The region of the window when it is shown for the first time.
+    /// Afterwards the window keeps the position and size it was moved to.
+    pub initial: Rect,
+    /// The smallest size the window can be resized to.
+    pub min_size: Size,
+    /// The layout of the content. Its placement is ignored, the content fills
+    /// the window below the title bar.
+    pub content: Layout,
+    pub resizable: bool,
+    pub collapsible: bool,
+    pub closable: bool,
+}
+
+impl Window {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        Window {
+            title: title.into(),
+            initial: Rect { x: 20.0, y: 20.0, w: 300.0, h: 200.0 },
+            min_size: Size { w: 100.0, h: 60.0 },
+            content: Layout::column().padding(5.0).spacing(5.0),
+            resizable: true,
+            collapsible: true,
+            closable: true,
+        }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.initial.x = x;
+        self.initial.y = y;
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.initial.w = w;
+        self.initial.h = h;
+        self
+    }
+
+    pub fn region(self, initial: Rect) -> Self {
+        Window { initial, .. self }
+    }
+
+    pub fn min_size(self, w: f64, h: f64) -> Self {
+        Window { min_size: Size { w, h }, .. self }
+    }
+
+    pub fn content(self, content: Layout) -> Self {
+        Window { content, .. self }
+    }
+
+    pub fn resizable(self, resizable: bool) -> Self {
+        Window { resizable, .. self }
+    }
+
+    pub fn collapsible(self, collapsible: bool) -> Self {
+        Window { collapsible, .. self }
+    }
+
+    pub fn closable(self, closable: bool) -> Self {
+        Window { closable, .. self }
+    }
+}
+
+/// The sides of a window moved by a resize handle.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub(crate) struct Sides {
+    pub left: bool,
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+}
+
+impl Sides {
+    const fn new(left: bool, top: bool, right: bool, bottom: bool) -> Self {
+        Sides { left, top, right, bottom }
+    }
+
+    /// The part of the window border covered by the handle for these sides.
+    pub fn handle(self, rect: Rect, border: f64) -> Rect {
+        let (x, w) = handle_span(self.left, self.right, rect.x, rect.w, border);
+        let (y, h) = handle_span(self.top, self.bottom, rect.y, rect.h, border);
+        Rect { x, y, w, h }
+    }
+
+    /// Move these sides of the rectangle by the distance the mouse was dragged,
+    /// without making it smaller than the minimum size.
+    pub fn resize(self, rect: Rect, delta: Point, min_size: Size) -> Rect {
+        let (x, w) = resize_span(self.left, self.right, rect.x, rect.w, delta.x, min_size.w);
+        let (y, h) = resize_span(self.top, self.bottom, rect.y, rect.h, delta.y, min_size.h);
+        Rect { x, y, w, h }
+    }
+}
+
+/// The resize handles along the edges, followed by the ones in the corners,
+/// such that corners take precedence where they overlap.
+pub(crate) const RESIZE_HANDLES: [Sides; 8] = [
+    Sides::new(true, false, false, false),
+    Sides::new(false, true, false, false),
+    Sides::new(false, false, true, false),
+    Sides::new(false, false, false, true),
+    Sides::new(true, true, false, false),
+    Sides::new(false, true, true, false),
+    Sides::new(true, false, false, true),
+    Sides::new(false, false, true, true),
+];
+
+fn handle_span(start: bool, end: bool, position: f64, length: f64, border: f64) -> (f64, f64) {
+    match (start, end) {
+        (true, false) => (position, border),
+        (false, true) => (position + length - border, border),
+        _ => (position, length),
+    }
+}
+
+fn resize_span(start: bool, end: bool, position: f64, length: f64, delta: f64, min: f64) -> (f64, f64) {
+    if start {
+        let moved = delta.min(length - min);
+        (position + moved, length - moved)
+    } else if end {
+        (position, (length + delta).max(min))
+    } else {
+        (position, length)
+    }
+}
+
+/// The state of a window that is kept across frames.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct WindowState {
+    pub rect: Rect,
+    pub collapsed: bool,
+    /// The mouse position and window region when moving or resizing the window started.
+    pub drag: Option<(Point, Rect)>,
+    /// The region covered by the window when it was last shown, which hides
+    /// the widgets below it from the mouse.
+    pub outline: Rect,
+    /// The layer the window was last shown in.
+    pub layer: Layer,
+}
+
+impl WindowState {
+    pub fn new(window: &Window) -> Self {
+        WindowState {
+            rect: window.initial,
+            collapsed: false,
+            drag: None,
+            outline: window.initial,
+            layer: Layer::Normal,
+        }
+    }
+}
+
+/// The order of the windows from bottom to top. The state of each window is
+/// kept in the widget states, and dropped once it was not shown for a while.
+#[derive(Default)]
+pub(crate) struct WindowStack {
+    /// The windows shown in the previous frame, the topmost one last.
+    order: Vec<Id>,
+}
+
+impl WindowStack {
+    /// Put the window on top of all other windows if it was not shown in the previous frame.
+    pub fn show(&mut self, id: Id) {
+        if ! self.order.contains(&id) {
+            self.order.push(id);
+        }
+    }
+
+    /// Put the window on top of all other windows.
+    pub fn raise(&mut self, id: Id) {
+        self.order.retain(|window| *window != id);
+        self.order.push(id);
+    }
+
+    /// The position of the window from the bottom.
+    pub fn depth(&self, id: Id) -> usize {
+        self.order.iter().position(|window| *window == id).unwrap_or(0)
+    }
+
+    pub fn topmost(&self) -> Option<Id> {
+        self.order.last().cloned()
+    }
+
+    /// The topmost window in the given layer that covers the given point.
+    pub fn window_at(&self, states: &WidgetStates, point: Point, layer: Layer) -> Option<Id> {
+        self.order.iter().rev()
+            .find(|id| states.peek::<WindowState>(**id).is_some_and(|state| state.layer == layer && state.outline.contains(point)))
+            .cloned()
+    }
+
+    /// Forget the order of the windows that were not shown in the current frame,
+    /// such that they no longer cover other widgets.
+    pub fn retain_shown(&mut self, shown: &[Id]) {
+        self.order.retain(|id| shown.contains(id));
+    }
+}
+
+/// The state of a window while its content is being created.
+pub(crate) struct WindowCursor {
+    pub id: Id,
+    /// Whether the window is open, in which case its draw commands are kept.
+    pub visible: bool,
+    /// Whether the window is expanded, in which case a layout for its content is open.
+    pub content: bool,
+    /// The draw commands, interaction clip and scroll areas of the enclosing
+    /// UI, which are restored when the window is closed.
+    pub parent_list: DisplayList,
+    pub parent_clip: Vec<Rect>,
+    pub parent_scrolls: Vec<ScrollCursor>,
+}
@@ -0,0 +1,303 @@
+//! A host that opens a window with glutin and renders glui with nanovg.
+
+use ::glutin::{ContextBuilder, ContextError, ControlFlow, CreationError, Event, EventsLoop, PossiblyCurrent, WindowBuilder, WindowEvent, WindowedContext};
+use ::glutin::dpi::LogicalSize;
+use crate::adapter::glutin::input_event;
+use crate::core::{self, Color, InputEvent, Renderer};
+use crate::recording::{Recorder, Recording};
+use crate::backend::nanovg::NanovgRenderer;
+use std::fmt;
+use std::time::Instant;
+
+/// An application driven by `GlutinHost::run`.
+pub trait App {
+    /// Build the UI for the current frame. May be called several times per
+    /// frame while the layout settles, see `Glui::run_frame`.
+    fn update<R: Renderer>(&mut self, frame: &mut core::GluiFrame<R>);
+}
+
+/// The reasons creating a `GlutinHost` can fail.
+#[derive(Debug)]
+pub enum HostError {
+    /// The window or its OpenGL context could not be created.
+    Creation(CreationError),
+    /// The OpenGL context could not be made current.
+    Context(ContextError),
+    /// The nanovg context could not be created.
+    Nanovg,
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HostError::Creation(error) => write!(f, "could not create the window: {}", error),
+            HostError::Context(error) => write!(f, "could not make the OpenGL context current: {}", error),
+            HostError::Nanovg => write!(f, "could not create the nanovg context"),
+        }
+    }
+}
+
+impl std::error::Error for HostError {}
+
+/// Configures the window of a `GlutinHost`, see `GlutinHost::builder`.
+#[derive(Clone, Debug)]
+pub struct GlutinHostBuilder {
+    title: String,
+    size: Option<core::Size>,
+    vsync: bool,
+    multisampling: u16,
+    clear_color: Color,
+}
+
+impl GlutinHostBuilder {
+    pub fn title<S: Into<String>>(self, title: S) -> Self {
+        GlutinHostBuilder {
+            title: title.into(),
+            .. self
+        }
+    }
+
+    /// The initial inner size of the window in logical units.
+    pub fn size(self, w: f64, h: f64) -> Self {
+        GlutinHostBuilder {
+            size: Some(core::Size { w, h }),
+            .. self
+        }
+    }
+
+    pub fn vsync(self, vsync: bool) -> Self {
+        GlutinHostBuilder {
+            vsync,
+            .. self
+        }
+    }
+
+    /// The number of samples used for multisample anti-aliasing, 0 disables it.
+    pub fn multisampling(self, samples: u16) -> Self {
+        GlutinHostBuilder {
+            multisampling: samples,
+            .. self
+        }
+    }
+
+    /// The color the window is cleared with before drawing a frame.
+    pub fn clear_color(self, clear_color: Color) -> Self {
+        GlutinHostBuilder {
+            clear_color,
+            .. self
+        }
+    }
+
+    /// Open the window and set up OpenGL and nanovg.
+    #[allow(clippy::redundant_field_names)]
+    pub fn build(self) -> Result<GlutinHost, HostError> {
+        let events_loop = EventsLoop::new();
+        let mut window = WindowBuilder::new()
+            .with_title(self.title);
+        if let Some(size) = self.size {
+            window = window.with_dimensions(LogicalSize::new(size.w, size.h));
+        }
+
+        let gl_context = ContextBuilder::new()
+            .with_vsync(self.vsync)
+            .with_multisampling(self.multisampling)
+            .with_srgb(true)
+            .build_windowed(window, &events_loop)
+            .map_err(HostError::Creation)?;
+
+        let gl_context = unsafe { gl_context.make_current().map_err(|(_, error)| HostError::Context(error))? };
+
+        let clear = self.clear_color;
+        unsafe {
+            gl::load_with(|symbol| gl_context.get_proc_address(symbol) as *const _);
+            gl::ClearColor(clear.r, clear.g, clear.b, clear.a);
+        }
+
+        let nanovg_context = nanovg::ContextBuilder::new()
+            .stencil_strokes()
+            .build()
+            .map_err(|_| HostError::Nanovg)?;
+
+        let host = GlutinHost {
+            events_loop: events_loop,
+            gl_context: gl_context,
+            nanovg_context: nanovg_context,
+            last_display_list: core::DisplayList::new(),
+            frame_changed: false,
+            recorder: None,
+            start: Instant::now(),
+        };
+
+        Ok(host)
+    }
+}
+
+/// Owns a window and drives glui with its events. Either call `run`, or
+/// `wait_events`, `render` and `submit_frame` in a loop of your own.
+pub struct GlutinHost {
+    gl_context: WindowedContext<PossiblyCurrent>,
+    events_loop: EventsLoop,
+    nanovg_context: nanovg::Context,
+    /// The draw commands of the most recently presented frame.
+    last_display_list: core::DisplayList,
+    /// Whether the last rendered frame differs from the presented one.
+    frame_changed: bool,
+    recorder: Option<Recorder>,
+    /// The start of the session, which input times are relative to.
+    start: Instant,
+}
+
+impl GlutinHost {
+    pub fn builder() -> GlutinHostBuilder {
+        GlutinHostBuilder {
+            title: String::from("glui"),
+            size: None,
+            vsync: true,
+            multisampling: 4,
+            clear_color: Color::new(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+
+    /// Open a window with the given title and the default settings.
+    pub fn new(title: &str) -> Result<Self, HostError> {
+        GlutinHost::builder().title(title).build()
+    }
+
+    /// Show the app until the window is closed or `GluiState::window_close` is set.
+    pub fn run<A: App>(&mut self, glui: &mut core::Glui, app: &mut A) {
+        while ! glui.uistate.window_close {
+            self.wait_events(glui);
+            self.render(glui, |frame| app.update(frame));
+            self.submit_frame();
+        }
+    }
+
+    pub fn add_font<S: AsRef<str>>(&mut self, name: S, data: &[u8]) -> nanovg::CreateFontResult<'_> {
+        nanovg::Font::from_memory(&self.nanovg_context, name, data)
+    }
+
+    /// Start recording all input events applied to the UI state.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new());
+    }
+
+    /// Stop recording and return the recorded session, if any.
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        self.recorder.take().map(Recorder::finish)
+    }
+
+    /// Block until at least one event arrived and apply all pending events to the UI state.
+    pub fn wait_events(&mut self, glui: &mut core::Glui) {
+        self.handle_events(glui, true, true);
+    }
+
+    /// Handle pending window events without blocking, while discarding all user input.
+    /// Used while replaying a recording.
+    pub fn poll_window_events(&mut self, glui: &mut core::Glui) {
+        self.handle_events(glui, false, false);
+    }
+
+    fn handle_events(&mut self, glui: &mut core::Glui, block: bool, apply_input: bool) {
+        let last_display_list = &mut self.last_display_list;
+        let recorder = &mut self.recorder;
+        let start = self.start;
+        let mut handle_event = |event| if let Event::WindowEvent { event, .. } = event {
+            let input = match event {
+                // The window contents need to be redrawn even if the UI didn't change
+                WindowEvent::Resized(_) | WindowEvent::Refresh | WindowEvent::HiDpiFactorChanged(_) => {
+                    last_display_list.clear();
+                    None
+                },
+                event => input_event(&event),
+            };
+
+            // Closing the window must always be possible, even while replaying
+            let close = input == Some(InputEvent::CloseRequested);
+            if let Some(input) = input.filter(|_| apply_input || close) {
+                // Clicks are counted based on the time they occurred
+                let time = match input {
+                    InputEvent::PointerButton(..) => Some(InputEvent::Time(start.elapsed().as_secs_f64())),
+                    _ => None,
+                };
+
+                for input in time.iter().chain(Some(&input)) {
+                    match recorder {
+                        Some(recorder) => recorder.apply(&mut glui.uistate, input),
+                        None => glui.feed_event(input.clone()),
+                    }
+                }
+            }
+        };
+        
+        if block {
+            // Wait for first event, then handle all remaining events in queue
+            self.events_loop.run_forever(|event| { handle_event(event); ControlFlow::Break });
+        }
+        self.events_loop.poll_events(&mut handle_event);
+    }
+
+    pub fn render<R, F>(&mut self, glui: &mut core::Glui, mut render: F) -> R where
+        F: FnMut(&mut core::GluiFrame<NanovgRenderer>) -> R
+    {
+        // Only then start rendering the UI
+        let window = self.gl_context.window();
+        let dpi_factor = window.get_hidpi_factor();
+        let logical_size = window.get_inner_size().unwrap();
+        let physical_size = logical_size.to_physical(dpi_factor);
+
+        let window_size = core::Size { w: logical_size.width, h: logical_size.height };
+        if glui.uistate.window_size != window_size {
+            let resized = InputEvent::Resized(window_size);
+            match &mut self.recorder {
+                Some(recorder) => recorder.apply(&mut glui.uistate, &resized),
+                None => glui.feed_event(resized),
+            }
+        }
+
+        let mut result = None;
+        let events_proxy = self.events_loop.create_proxy();
+        let last_display_list = &mut self.last_display_list;
+        let frame_changed = &mut self.frame_changed;
+
+        self.nanovg_context.frame(
+            (logical_size.width as f32, logical_size.height as f32),
+            dpi_factor as f32,
+            |frame| {
+                let mut renderer = NanovgRenderer::new(frame);
+                let output = glui.run_frame(&mut renderer, &mut render);
+
+                if output.redraw {
+                    // Queue event in order to prevent the next wait_events from blocking
+                    events_proxy.wakeup().expect("The event loop should be alive here");
+                }
+
+                // Identical frames don't need to be drawn and presented again
+                let display_list = output.display_list;
+                *frame_changed = display_list.is_empty() || display_list != *last_display_list;
+                if *frame_changed {
+                    unsafe {
+                        gl::Viewport(0, 0, physical_size.width as i32, physical_size.height as i32);
+                        gl::Clear(
+                            gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT,
+                        );
+                    }
+                    display_list.replay(&mut renderer);
+                    *last_display_list = display_list;
+                }
+                result = Some(output.value);
+            }
+        );
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.next_frame();
+        }
+
+        result.unwrap()
+    }
+
+    pub fn submit_frame(&mut self) {
+        if self.frame_changed {
+            self.gl_context.swap_buffers().unwrap();
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! Ready-made hosts that open a window, feed its events to glui and present
+//! the rendered frames, each behind a feature named after the windowing library.
+
+#[cfg(feature = "glutin-host")]
+pub mod glutin;
@@ -1,2 +1,7 @@
 pub mod core;
-pub mod widgets;
\ No newline at end of file
+pub mod widgets;
+pub mod backend;
+pub mod adapter;
+pub mod host;
+pub mod recording;
+pub mod testing;
@@ -0,0 +1,142 @@
+//! Recording of input sessions and their deterministic replay.
+//!
+//! A `Recorder` captures every `InputEvent` applied to the `GluiState` together
+//! with the number of the frame it was applied before. The resulting `Recording`
+//! can be saved as a `.gluirec` file and fed back frame by frame by a `Replayer`.
+
+use crate::core::{GluiState, InputEvent};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+/// The file extension commonly used for saved recordings.
+pub const FILE_EXTENSION: &str = "gluirec";
+
+/// An input event together with the moment it occurred.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// The number of the frame before which the event was applied.
+    pub frame: u64,
+    /// Milliseconds since the recording started.
+    pub time_ms: u64,
+    pub event: InputEvent,
+}
+
+/// A recorded input session.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Recording {
+    /// The total number of frames rendered during the session.
+    pub frames: u64,
+    /// All events in the order they were applied.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Recording> {
+        let file = std::fs::File::open(path)?;
+        serde_json::from_reader(io::BufReader::new(file)).map_err(io::Error::from)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        serde_json::to_writer(io::BufWriter::new(file), self).map_err(io::Error::from)
+    }
+}
+
+/// Captures the input events of a running session.
+pub struct Recorder {
+    recording: Recording,
+    start: Instant,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder::new()
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder {
+            recording: Recording::default(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Record an event that is applied before the current frame.
+    pub fn record(&mut self, event: &InputEvent) {
+        self.recording.events.push(RecordedEvent {
+            frame: self.recording.frames,
+            time_ms: self.start.elapsed().as_millis() as u64,
+            event: event.clone(),
+        });
+    }
+
+    /// Apply the event to the state and record it.
+    pub fn apply(&mut self, state: &mut GluiState, event: &InputEvent) {
+        self.record(event);
+        state.apply(event);
+    }
+
+    /// Should be called after each rendered frame, such that subsequent
+    /// events are attributed to the next frame.
+    pub fn next_frame(&mut self) {
+        self.recording.frames += 1;
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// Feeds a recording back into a `GluiState` one frame at a time.
+pub struct Replayer {
+    recording: Recording,
+    /// Index of the next event to be applied.
+    position: usize,
+    /// The number of the next frame.
+    frame: u64,
+}
+
+impl Replayer {
+    pub fn new(recording: Recording) -> Self {
+        Replayer {
+            recording,
+            position: 0,
+            frame: 0,
+        }
+    }
+
+    /// Apply all events recorded before the next frame and advance to the
+    /// following frame. Returns false once all recorded frames have been replayed.
+    pub fn apply_frame(&mut self, state: &mut GluiState) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+
+        while let Some(recorded) = self.recording.events.get(self.position) {
+            if recorded.frame > self.frame {
+                break;
+            }
+            state.apply(&recorded.event);
+            self.position += 1;
+        }
+
+        self.frame += 1;
+        true
+    }
+
+    /// The number of the frame that is replayed next.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frames && self.position >= self.recording.events.len()
+    }
+}
@@ -0,0 +1,225 @@
+//! Drive a `Glui` instance with synthetic input, without requiring a window.
+
+mod snapshot;
+
+pub use snapshot::*;
+
+use crate::backend::software::{Image, SoftwareRenderer};
+use crate::core::{ButtonState, Color, DisplayList, DuplicateId, Glui, GluiFrame, Id, InputEvent, KeyEvent, Modifiers, PointerButton, Point, ScrollDelta, Size, Key};
+use crate::recording::{Recording, Replayer};
+use std::path::Path;
+
+/// Owns a `Glui` and a software renderer and runs frames on behalf of a test.
+///
+/// Input is applied to the `GluiState` the same way a host would do it,
+/// and takes effect in the next call to `frame`.
+pub struct Harness {
+    glui: Glui,
+    renderer: SoftwareRenderer,
+    /// The color the image is cleared with before rendering a frame.
+    background: Color,
+    /// The time in seconds that passes with each frame.
+    frame_time: f64,
+}
+
+/// The outcome of a single frame run by the `Harness`.
+pub struct FrameOutput<T> {
+    /// The value returned by the frame closure.
+    pub value: T,
+    /// The widget below the cursor at the end of the frame.
+    pub hover: Option<Id>,
+    /// The widget on which the left mouse button was pressed.
+    pub active: Option<Id>,
+    /// The widget that has the keyboard focus.
+    pub focus: Option<Id>,
+    /// Whether a widget asked for the UI to be redrawn immediately.
+    pub redraw: bool,
+    pub display_list: DisplayList,
+    /// The IDs used by more than one widget, see `FrameResult::duplicate_ids`.
+    pub duplicate_ids: Vec<DuplicateId>,
+}
+
+impl Harness {
+    /// Create a harness with a logical screen of the given size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Harness::with_pixel_ratio(width, height, 1.0)
+    }
+
+    /// Create a harness with a logical screen of the given size, whose rendered
+    /// images are `pixel_ratio` times larger in both dimensions.
+    pub fn with_pixel_ratio(width: u32, height: u32, pixel_ratio: f32) -> Self {
+        let mut glui = Glui::new();
+        glui.uistate.apply(&InputEvent::Resized(Size { w: f64::from(width), h: f64::from(height) }));
+
+        Harness {
+            glui,
+            renderer: SoftwareRenderer::new(width, height, pixel_ratio),
+            background: Color::from_rgb(255, 255, 255),
+            frame_time: 1.0 / 60.0,
+        }
+    }
+
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    /// Set the time that passes with each frame, 1/60 s by default.
+    pub fn set_frame_time(&mut self, seconds: f64) {
+        self.frame_time = seconds;
+    }
+
+    /// Register a TrueType font, which is required for meaningful text measurements.
+    pub fn add_font<S: Into<String>>(&mut self, name: S, data: &[u8]) -> Result<(), rusttype::Error> {
+        self.renderer.add_font(name, data)
+    }
+
+    pub fn glui(&self) -> &Glui {
+        &self.glui
+    }
+
+    pub fn glui_mut(&mut self) -> &mut Glui {
+        &mut self.glui
+    }
+
+    pub fn renderer(&self) -> &SoftwareRenderer {
+        &self.renderer
+    }
+
+    pub fn renderer_mut(&mut self) -> &mut SoftwareRenderer {
+        &mut self.renderer
+    }
+
+    /// Apply an input event, just like a host would do it.
+    pub fn apply(&mut self, event: &InputEvent) {
+        self.glui.feed_event(event.clone());
+    }
+
+    pub fn mouse_move(&mut self, x: f64, y: f64) {
+        self.apply(&InputEvent::MouseMove(Point { x, y }));
+    }
+
+    /// Press the left mouse button.
+    pub fn mouse_down(&mut self) {
+        self.button_down(PointerButton::Left);
+    }
+
+    /// Release the left mouse button.
+    pub fn mouse_up(&mut self) {
+        self.button_up(PointerButton::Left);
+    }
+
+    pub fn button_down(&mut self, button: PointerButton) {
+        self.apply(&InputEvent::PointerButton(button, ButtonState::Pressed));
+    }
+
+    pub fn button_up(&mut self, button: PointerButton) {
+        self.apply(&InputEvent::PointerButton(button, ButtonState::Released));
+    }
+
+    /// Let time pass in addition to the frame time, e.g. in order to separate
+    /// two clicks that would otherwise count as a double click.
+    pub fn advance_time(&mut self, seconds: f64) {
+        let time = self.glui.uistate.time + seconds;
+        self.apply(&InputEvent::Time(time));
+    }
+
+    /// Scroll by the given distance in logical units, as if a trackpad was used.
+    /// Positive values move the content to the bottom right.
+    pub fn scroll(&mut self, x: f64, y: f64) {
+        self.apply(&InputEvent::Scroll(ScrollDelta::Pixels(Point { x, y })));
+    }
+
+    /// Scroll by the given number of lines, as if the mouse wheel was turned.
+    pub fn scroll_lines(&mut self, x: f64, y: f64) {
+        self.apply(&InputEvent::Scroll(ScrollDelta::Lines(Point { x, y })));
+    }
+
+    /// Queue a key event with the given state.
+    pub fn key(&mut self, key: Key, modifiers: Modifiers, state: ButtonState) {
+        self.apply(&InputEvent::Key(KeyEvent { key, modifiers, state }));
+    }
+
+    /// Queue a key press immediately followed by its release.
+    pub fn key_press(&mut self, key: Key, modifiers: Modifiers) {
+        self.key(key, modifiers, ButtonState::Pressed);
+        self.key(key, modifiers, ButtonState::Released);
+    }
+
+    /// Queue text input, as if the characters were typed on the keyboard.
+    pub fn type_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            self.apply(&InputEvent::Text(ch));
+        }
+    }
+
+    pub fn request_close(&mut self) {
+        self.apply(&InputEvent::CloseRequested);
+    }
+
+    /// Run the closure as one frame and report the resulting UI state.
+    ///
+    /// Like a host, the frame is built with `Glui::run_frame`, and only the
+    /// output of its last layout pass is reported.
+    pub fn frame<T, F>(&mut self, ui: F) -> FrameOutput<T> where
+        F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T
+    {
+        let result = self.glui.run_frame(&mut self.renderer, ui);
+        self.advance_time(self.frame_time);
+
+        let uistate = &self.glui.uistate;
+        FrameOutput {
+            value: result.value,
+            hover: uistate.hover_widget,
+            active: uistate.active_widget,
+            focus: uistate.focus_widget,
+            redraw: result.redraw,
+            display_list: result.display_list,
+            duplicate_ids: result.duplicate_ids,
+        }
+    }
+
+    /// Run the closure as one frame like `frame` and rasterize the result.
+    pub fn render<T, F>(&mut self, ui: F) -> (FrameOutput<T>, Image) where
+        F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T
+    {
+        let output = self.frame(ui);
+        self.renderer.clear(self.background);
+        output.display_list.replay(&mut self.renderer);
+        (output, self.renderer.image().clone())
+    }
+
+    /// Render the closure as one frame and compare the image against the golden image
+    /// at the given path. See `assert_snapshot` for details.
+    pub fn assert_snapshot<T, F, P>(&mut self, golden: P, tolerance: Tolerance, ui: F) -> FrameOutput<T> where
+        F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T,
+        P: AsRef<Path>
+    {
+        let (output, image) = self.render(ui);
+        assert_snapshot(&image, golden, tolerance);
+        output
+    }
+
+    /// Replay a recorded session, running one frame for each recorded frame.
+    pub fn replay<T, F>(&mut self, recording: Recording, mut ui: F) -> Vec<FrameOutput<T>> where
+        F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T
+    {
+        let mut replayer = Replayer::new(recording);
+        let mut outputs = Vec::new();
+        while replayer.apply_frame(&mut self.glui.uistate) {
+            outputs.push(self.frame(&mut ui));
+        }
+        outputs
+    }
+
+    /// Click at the given position, which takes one frame for pressing and one
+    /// for releasing the mouse button. Returns the output of the second frame.
+    pub fn click<T, F>(&mut self, x: f64, y: f64, mut ui: F) -> FrameOutput<T> where
+        F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T
+    {
+        self.mouse_move(x, y);
+        self.mouse_down();
+        self.frame(&mut ui);
+        self.mouse_up();
+        self.frame(&mut ui)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::backend::software::Image;
+use std::path::{Path, PathBuf};
+
+/// Name of the environment variable which, when set to `1`, makes `assert_snapshot`
+/// overwrite the golden images with the actual rendering instead of comparing them.
+pub const BLESS_VAR: &str = "GLUI_BLESS";
+
+/// How much a rendering may deviate from its golden image.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Tolerance {
+    /// The maximum difference per color channel for two pixels to be considered equal.
+    pub channel: u8,
+    /// The number of pixels that may differ by more than `channel`.
+    pub pixels: usize,
+}
+
+impl Tolerance {
+    pub fn exact() -> Self {
+        Tolerance {
+            channel: 0,
+            pixels: 0,
+        }
+    }
+}
+
+impl Default for Tolerance {
+    /// Allows for minor differences in anti-aliasing.
+    fn default() -> Self {
+        Tolerance {
+            channel: 2,
+            pixels: 0,
+        }
+    }
+}
+
+/// The result of comparing an image with its expected version.
+pub struct Comparison {
+    /// Number of pixels that differ by more than the channel tolerance.
+    pub mismatched: usize,
+    /// The expected image dimmed, with mismatching pixels highlighted in red.
+    pub diff: Image,
+}
+
+/// Compare two images pixel by pixel. Returns `None` if their sizes differ.
+pub fn compare(expected: &Image, actual: &Image, tolerance: Tolerance) -> Option<Comparison> {
+    if expected.width() != actual.width() || expected.height() != actual.height() {
+        return None;
+    }
+
+    let mut diff = expected.clone();
+    let mut mismatched = 0;
+
+    for y in 0 .. expected.height() {
+        for x in 0 .. expected.width() {
+            let a = expected.pixel(x, y);
+            let b = actual.pixel(x, y);
+            let differs = a.iter().zip(&b)
+                .any(|(a, b)| (i16::from(*a) - i16::from(*b)).unsigned_abs() > u16::from(tolerance.channel));
+
+            let pixel = if differs {
+                mismatched += 1;
+                [255, 0, 0, 255]
+            } else {
+                let gray = ((u16::from(a[0]) + u16::from(a[1]) + u16::from(a[2])) / 3) as u8;
+                [gray, gray, gray, a[3] / 4]
+            };
+            diff.set_pixel(x, y, pixel);
+        }
+    }
+
+    Some(Comparison {
+        mismatched,
+        diff,
+    })
+}
+
+/// Assert that the image matches the golden PNG image at the given path.
+///
+/// On failure, the actual image and a diff image are written next to the golden
+/// image with the extensions `.actual.png` and `.diff.png` respectively. If the
+/// `GLUI_BLESS` environment variable is set to `1`, the golden image is created
+/// or replaced by the actual image instead.
+pub fn assert_snapshot<P: AsRef<Path>>(actual: &Image, golden: P, tolerance: Tolerance) {
+    let golden = golden.as_ref();
+
+    if std::env::var(BLESS_VAR).map(|value| value == "1").unwrap_or(false) {
+        if let Some(parent) = golden.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|err| panic!("cannot create {}: {}", parent.display(), err));
+        }
+        actual.save_png(golden)
+            .unwrap_or_else(|err| panic!("cannot write golden image {}: {}", golden.display(), err));
+        return;
+    }
+
+    let expected = Image::load_png(golden).unwrap_or_else(|err| panic!(
+        "cannot read golden image {}: {} (run with {}=1 to create it)",
+        golden.display(), err, BLESS_VAR
+    ));
+
+    let actual_path = sibling_path(golden, "actual");
+    let diff_path = sibling_path(golden, "diff");
+
+    match compare(&expected, actual, tolerance) {
+        None => {
+            let _ = actual.save_png(&actual_path);
+            panic!(
+                "snapshot {} has size {}x{}, but the rendering has size {}x{} (actual image written to {})",
+                golden.display(), expected.width(), expected.height(),
+                actual.width(), actual.height(), actual_path.display()
+            );
+        }
+        Some(comparison) if comparison.mismatched > tolerance.pixels => {
+            let _ = actual.save_png(&actual_path);
+            let _ = comparison.diff.save_png(&diff_path);
+            panic!(
+                "snapshot {} differs in {} pixels (tolerance {:?}), see {} and {}",
+                golden.display(), comparison.mismatched, tolerance,
+                actual_path.display(), diff_path.display()
+            );
+        }
+        Some(_) => {
+            // Remove leftovers of previous failures
+            let _ = std::fs::remove_file(&actual_path);
+            let _ = std::fs::remove_file(&diff_path);
+        }
+    }
+}
+
+/// Turns `dir/name.png` into `dir/name.<suffix>.png`.
+fn sibling_path(golden: &Path, suffix: &str) -> PathBuf {
+    let stem = golden.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    golden.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Color;
+
+    #[test]
+    fn compare_counts_pixels_beyond_channel_tolerance() {
+        let expected = Image::new(4, 4, Color::from_rgb(100, 100, 100));
+        let mut actual = expected.clone();
+        actual.set_pixel(0, 0, [102, 100, 100, 255]);
+        actual.set_pixel(3, 3, [100, 100, 103, 255]);
+
+        let exact = compare(&expected, &actual, Tolerance::exact()).unwrap();
+        assert_eq!(exact.mismatched, 2);
+        assert_eq!(exact.diff.pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(exact.diff.pixel(1, 0), [100, 100, 100, 63]);
+
+        let default = compare(&expected, &actual, Tolerance::default()).unwrap();
+        assert_eq!(default.mismatched, 1);
+        assert_eq!(default.diff.pixel(0, 0), [100, 100, 100, 63]);
+        assert_eq!(default.diff.pixel(3, 3), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn compare_rejects_different_sizes() {
+        let expected = Image::new(4, 4, Color::from_rgb(0, 0, 0));
+        let actual = Image::new(4, 3, Color::from_rgb(0, 0, 0));
+        assert!(compare(&expected, &actual, Tolerance::exact()).is_none());
+    }
+
+    #[test]
+    fn sibling_path_adds_suffix() {
+        let path = sibling_path(Path::new("tests/golden/button.png"), "diff");
+        assert_eq!(path, Path::new("tests/golden/button.diff.png"));
+    }
+}
@@ -1,78 +1,108 @@
-use crate::core::{Id, Rect, GluiFrame, VirtualKeyCode};
+use crate::core::{Placement, Sizing, SizeHint, Id, Rect, Size, GluiFrame, Renderer, Response, Sense, Widget, PointerButton, Key};
 
 pub struct Button<S> {
     label: S,
-    region: Rect,
+    id: Option<Id>,
+    placement: Placement,
 }
 
 impl<S> Button<S> where
     S: AsRef<str>
 {
+    #[allow(clippy::redundant_field_names)]
     pub fn with_label(label: S) -> Button<S> {
         Button {
             label: label,
-            region: Rect::zero(),
+            id: None,
+            placement: Placement::default(),
         }
     }
+
+    /// The ID of the button, which is derived from its label unless given.
+    pub fn id(self, id: Id) -> Self {
+        Button { id: Some(id), .. self }
+    }
     
     pub fn at(mut self, x: f64, y: f64) -> Self {
-        self.region = Rect { x: x, y: y, .. self.region };
+        self.placement = self.placement.at(x, y);
         self
     }
     
     pub fn size(mut self, w: f64, h: f64) -> Self {
-        self.region = Rect { w: w, h: h, .. self.region };
+        self.placement = self.placement.size(w, h);
         self
     }
 
     pub fn region(mut self, region: Rect) -> Self {
-        self.region = region;
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
         self
     }
 
-    pub fn reify(self, id: Id, frame: &mut GluiFrame) -> bool {
-        let mut clicked = frame.clickable_widget(id, self.region);
-        let focused = frame.focusable_widget(id, clicked, |key_event| {
-            if key_event.state.is_pressed() && key_event.key == VirtualKeyCode::Return {
-                clicked = true;
+    /// Show the button with the given ID, see `Widget::ui`.
+    pub fn reify<R: Renderer>(self, id: Id, frame: &mut GluiFrame<R>) -> Response {
+        self.id(id).ui(frame)
+    }
+}
+
+impl<S> Widget for Button<S> where
+    S: AsRef<str>
+{
+    /// Show the button. Pressing Return while it is focused clicks it as well.
+    fn ui<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
+        let id = self.id.unwrap_or_else(|| Id::from_key(self.label.as_ref()));
+        let id = frame.scoped_id(id);
+
+        let text_bounds = frame.text_measure(self.label.as_ref());
+        let padding = frame.style().widget_padding;
+        let natural = Size { w: text_bounds.w + 2.0 * padding, h: text_bounds.h + 2.0 * padding };
+        let region = frame.place(self.placement, SizeHint::at_least(natural));
+
+        let mut activated = false;
+        let mut response = frame.interact_with_keys(id, region, Sense::focus(), |key_event| {
+            if key_event.state.is_pressed() && key_event.key == Key::Enter {
+                activated = true;
             }
             true
         });
+        if activated && ! response.clicked() {
+            response.clicks.push(PointerButton::Left);
+        }
+        let focused = response.has_focus;
 
-        let text_bounds = frame.text_measure(self.label.as_ref());
-        let text_rect = self.region.center(text_bounds);
-
-        frame.nanovg().path(|path| {
-            let border = &frame.style().widget_border;
-
-            path.rounded_rect(
-                (self.region.x as f32, self.region.y as f32),
-                (self.region.w as f32, self.region.h as f32),
-                border.radius,
-            );
-            
-            let is_active = frame.uistate().is_active(id);
-            let is_hover = frame.uistate().is_hover(id);
-
-            let fill_color = if is_active && is_hover {
-                frame.style().clickable_active
-            } else if is_active || is_hover {
-                frame.style().clickable_hover
-            } else {
-                frame.style().clickable_normal
-            };
-
-            let width = if focused { 2.0 } else { 1.0 };
-
-            path.fill(fill_color, nanovg::FillOptions::default());
-            path.stroke(border.color, nanovg::StrokeOptions {
-                width: width,
-                .. Default::default()
-            });
-        }, nanovg::PathOptions::default());
-
-        frame.text_render(self.label.as_ref(), text_rect.top_left(), frame.style().foreground_color);
-
-        clicked
+        let text_rect = region.center(text_bounds);
+
+        let is_active = frame.uistate().is_active(id);
+        let is_hover = response.hovered;
+
+        let style = frame.style();
+        let fill_color = if is_active && is_hover {
+            style.clickable_active
+        } else if is_active || is_hover {
+            style.clickable_hover
+        } else {
+            style.clickable_normal
+        };
+        let border_radius = style.widget_border.radius;
+        let border_color = style.widget_border.color;
+        let text_color = style.foreground_color;
+
+        let width = if focused { 2.0 } else { 1.0 };
+
+        frame.fill_rect(region, border_radius, fill_color);
+        frame.stroke_rect(region, border_radius, width, border_color);
+
+        frame.text_render(self.label.as_ref(), text_rect.top_left(), text_color);
+
+        response
     }
 }
@@ -0,0 +1,140 @@
+use crate::core::{Align, Layout, Placement, Sizing, SizeHint, Id, Point, Rect, Size, GluiFrame, Renderer};
+use super::{Button, Label};
+
+/// How a dialog was closed.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DialogResult {
+    /// The button with the given index was clicked.
+    Button(usize),
+    /// The dialog was dismissed with Escape.
+    Dismissed,
+}
+
+/// The answer to a confirmation dialog, see `confirm`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Confirmation {
+    Confirmed,
+    /// The cancel button was clicked or the dialog was dismissed with Escape.
+    Cancelled,
+}
+
+/// A modal dialog with a title, some content and a row of buttons, which is
+/// centered in the window above the rest of the UI. See `GluiFrame::begin_modal`.
+pub struct Dialog<S> {
+    title: S,
+    buttons: Vec<String>,
+}
+
+impl<S> Dialog<S> where
+    S: AsRef<str>
+{
+    pub fn new(title: S) -> Dialog<S> {
+        Dialog {
+            title,
+            buttons: Vec::new(),
+        }
+    }
+
+    /// The labels of the buttons, from left to right. Any button closes the dialog.
+    pub fn buttons<B: Into<String>>(self, buttons: Vec<B>) -> Self {
+        Dialog {
+            buttons: buttons.into_iter().map(Into::into).collect(),
+            .. self
+        }
+    }
+
+    /// Show the dialog while `open` is set, calling `build` to create its content.
+    /// Clicking a button or pressing Escape clears `open` and returns how the
+    /// dialog was closed.
+    pub fn reify<R, F>(self, id: Id, frame: &mut GluiFrame<R>, open: &mut bool, build: F) -> Option<DialogResult> where
+        R: Renderer,
+        F: FnOnce(&mut GluiFrame<R>)
+    {
+        if ! *open {
+            return None;
+        }
+        if frame.begin_modal(id) {
+            frame.end_modal();
+            *open = false;
+            return Some(DialogResult::Dismissed);
+        }
+
+        let style = frame.style();
+        let padding = style.widget_padding;
+        let title_height = style.window_title_height;
+        let min_width = style.dialog_min_width;
+        let auto = |layout: Layout| layout.width(Sizing::Auto).height(Sizing::Auto).align(Align::Stretch);
+
+        let region = frame.begin_layout(auto(Layout::column()));
+        let style = frame.style();
+        let (background, title_color) = (style.window_background, style.window_title_active);
+        let (radius, border_color) = (style.widget_border.radius, style.widget_border.color);
+        frame.fill_rect(region, radius, background);
+
+        let title_bounds = frame.text_measure(self.title.as_ref());
+        let title = frame.place(Placement::default(), SizeHint::at_least(Size {
+            w: title_bounds.w.max(min_width - 2.0 * padding) + 2.0 * padding,
+            h: title_height,
+        }));
+        frame.fill_rect(title, radius, title_color);
+        let text_position = Point {
+            x: title.x + padding,
+            .. title.center(title_bounds).top_left()
+        };
+        let text_color = frame.style().foreground_color;
+        frame.text_render(self.title.as_ref(), text_position, text_color);
+
+        let mut result = None;
+        frame.layout(auto(Layout::column().padding(padding).spacing(padding)), |frame| {
+            frame.layout(Layout::column().spacing(padding).width(Sizing::Auto).height(Sizing::Auto), build);
+
+            // The buttons are right aligned below the content
+            let sizes: Vec<Size> = self.buttons.iter()
+                .map(|label| {
+                    let bounds = frame.text_measure(label);
+                    Size { w: bounds.w + 2.0 * padding, h: bounds.h + 2.0 * padding }
+                })
+                .collect();
+            let total = sizes.iter().map(|size| size.w).sum::<f64>() + padding * sizes.len().saturating_sub(1) as f64;
+            let height = sizes.iter().map(|size| size.h).fold(0.0, f64::max);
+            let row = frame.place(Placement::default(), SizeHint::at_least(Size { w: total, h: height }));
+
+            let mut x = row.x + row.w - total;
+            for (index, (label, size)) in self.buttons.iter().zip(sizes).enumerate() {
+                if Button::with_label(label).region(Rect { x, y: row.y, w: size.w, h: size.h }).reify(id.with(&index), frame).clicked() {
+                    result = Some(DialogResult::Button(index));
+                }
+                x += size.w + padding;
+            }
+        });
+
+        frame.stroke_rect(region, radius, 1.0, border_color);
+        frame.end_layout();
+        frame.end_modal();
+
+        if result.is_some() {
+            *open = false;
+        }
+        result
+    }
+}
+
+/// Show a message with an OK button while `open` is set.
+/// Returns how the message was closed in the frame it was closed.
+pub fn message_box<R: Renderer>(id: Id, frame: &mut GluiFrame<R>, open: &mut bool, title: &str, message: &str) -> Option<DialogResult> {
+    Dialog::new(title)
+        .buttons(vec!["OK"])
+        .reify(id, frame, open, |frame| { Label::new(message).reify(frame); })
+}
+
+/// Ask for confirmation with an OK and a Cancel button while `open` is set.
+/// Returns the answer in the frame the dialog was closed.
+pub fn confirm<R: Renderer>(id: Id, frame: &mut GluiFrame<R>, open: &mut bool, title: &str, message: &str) -> Option<Confirmation> {
+    let result = Dialog::new(title)
+        .buttons(vec!["OK", "Cancel"])
+        .reify(id, frame, open, |frame| { Label::new(message).reify(frame); });
+    result.map(|result| match result {
+        DialogResult::Button(0) => Confirmation::Confirmed,
+        _ => Confirmation::Cancelled,
+    })
+}
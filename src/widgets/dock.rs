@@ -0,0 +1,583 @@
+use crate::core::{Layer, Layout, Orientation, Placement, Sizing, SizeHint, ScrollArea, Window, Id, Point, Rect, Size, GluiFrame, Renderer, PointerButton, Sense};
+use serde::{Deserialize, Serialize};
+
+/// Where a panel is docked relative to the panels it is dropped onto.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum DockPosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// As another tab next to the existing ones.
+    Center,
+}
+
+/// A node in the tree of docked panels.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum DockNode {
+    /// Panels shown as tabs, of which only the selected one is visible.
+    Tabs {
+        panels: Vec<String>,
+        selected: usize,
+    },
+    /// Two nodes next to each other, separated by a draggable divider.
+    Split {
+        orientation: Orientation,
+        /// The share of the first node in the extent of the split.
+        ratio: f64,
+        first: Box<DockNode>,
+        second: Box<DockNode>,
+    },
+}
+
+impl DockNode {
+    pub fn tabs<S: Into<String>>(panels: Vec<S>) -> Self {
+        DockNode::Tabs {
+            panels: panels.into_iter().map(Into::into).collect(),
+            selected: 0,
+        }
+    }
+
+    pub fn split(orientation: Orientation, ratio: f64, first: DockNode, second: DockNode) -> Self {
+        DockNode::Split {
+            orientation,
+            ratio,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    fn contains(&self, panel: &str) -> bool {
+        match self {
+            DockNode::Tabs { panels, .. } => panels.iter().any(|name| name == panel),
+            DockNode::Split { first, second, .. } => first.contains(panel) || second.contains(panel),
+        }
+    }
+
+    /// Remove the panel from the tree. Returns the remaining tree, in which
+    /// splits with an empty side are replaced by their other side.
+    fn remove(self, panel: &str) -> Option<DockNode> {
+        match self {
+            DockNode::Tabs { mut panels, selected } => {
+                let index = panels.iter().position(|name| name == panel);
+                if let Some(index) = index {
+                    panels.remove(index);
+                }
+                if panels.is_empty() {
+                    return None;
+                }
+                let selected = match index {
+                    Some(index) if index < selected => selected - 1,
+                    _ => selected.min(panels.len() - 1),
+                };
+                Some(DockNode::Tabs { panels, selected })
+            }
+            DockNode::Split { orientation, ratio, first, second } => {
+                match (first.remove(panel), second.remove(panel)) {
+                    (Some(first), Some(second)) => Some(DockNode::split(orientation, ratio, first, second)),
+                    (Some(node), None) | (None, Some(node)) => Some(node),
+                    (None, None) => None,
+                }
+            }
+        }
+    }
+
+    /// Dock the panel at the given position relative to the tabs containing the target.
+    /// Returns whether the target was found.
+    fn insert(&mut self, target: &str, panel: String, position: DockPosition) -> bool {
+        match self {
+            DockNode::Split { first, second, .. } =>
+                first.insert(target, panel.clone(), position) || second.insert(target, panel, position),
+            DockNode::Tabs { panels, selected } => {
+                if ! panels.iter().any(|name| name == target) {
+                    return false;
+                }
+                if position == DockPosition::Center {
+                    panels.push(panel);
+                    *selected = panels.len() - 1;
+                    return true;
+                }
+
+                let existing = std::mem::replace(self, DockNode::tabs(Vec::<String>::new()));
+                let docked = DockNode::tabs(vec![panel]);
+                *self = match position {
+                    DockPosition::Left => DockNode::split(Orientation::Horizontal, 0.5, docked, existing),
+                    DockPosition::Right => DockNode::split(Orientation::Horizontal, 0.5, existing, docked),
+                    DockPosition::Top => DockNode::split(Orientation::Vertical, 0.5, docked, existing),
+                    DockPosition::Bottom | DockPosition::Center => DockNode::split(Orientation::Vertical, 0.5, existing, docked),
+                };
+                true
+            }
+        }
+    }
+
+    /// The node at the given path, where 0 selects the first and 1 the second child of a split.
+    fn node(&self, path: &[usize]) -> Option<&DockNode> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (Some((0, rest)), DockNode::Split { first, .. }) => first.node(rest),
+            (Some((_, rest)), DockNode::Split { second, .. }) => second.node(rest),
+            (Some(_), DockNode::Tabs { .. }) => None,
+        }
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut DockNode> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (Some((0, rest)), DockNode::Split { first, .. }) => first.node_mut(rest),
+            (Some((_, rest)), DockNode::Split { second, .. }) => second.node_mut(rest),
+            (Some(_), DockNode::Tabs { .. }) => None,
+        }
+    }
+}
+
+/// A panel that has been undocked into a floating window.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FloatingPanel {
+    pub panel: String,
+    pub region: Rect,
+}
+
+/// The arrangement of the panels in a `DockSpace`, which is updated as the user
+/// drags panels around and can be saved and restored with `to_json` and `from_json`.
+///
+/// Panels are identified by their names, which are also shown in their tabs.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct DockLayout {
+    pub root: Option<DockNode>,
+    pub floating: Vec<FloatingPanel>,
+    /// The tab that is currently being dragged.
+    #[serde(skip)]
+    drag: Option<TabDrag>,
+    /// The path of the split whose divider is dragged, with the mouse position
+    /// along the split and the ratio when dragging started.
+    #[serde(skip)]
+    resize: Option<(Vec<usize>, f64, f64)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct TabDrag {
+    panel: String,
+    start: Point,
+    /// Whether the mouse moved far enough for the tab to be dropped elsewhere.
+    moved: bool,
+}
+
+impl DockLayout {
+    pub fn new(root: DockNode) -> Self {
+        DockLayout {
+            root: Some(root),
+            .. DockLayout::default()
+        }
+    }
+
+    pub fn contains(&self, panel: &str) -> bool {
+        self.root.as_ref().is_some_and(|root| root.contains(panel))
+            || self.floating.iter().any(|floating| floating.panel == panel)
+    }
+
+    /// Dock the panel at the given position relative to the tabs containing the
+    /// target panel. Returns false without changing anything if the target is
+    /// not docked or the same as the panel.
+    pub fn dock(&mut self, panel: &str, target: &str, position: DockPosition) -> bool {
+        let docked = self.root.as_ref().is_some_and(|root| root.contains(target));
+        if panel == target || ! docked {
+            return false;
+        }
+        self.remove(panel);
+        match &mut self.root {
+            Some(root) => root.insert(target, panel.to_owned(), position),
+            None => false,
+        }
+    }
+
+    /// Move the panel into a floating window with the given region.
+    pub fn undock(&mut self, panel: &str, region: Rect) {
+        self.remove(panel);
+        self.floating.push(FloatingPanel { panel: panel.to_owned(), region });
+    }
+
+    /// Remove the panel from the layout. Returns whether it was part of the layout.
+    pub fn remove(&mut self, panel: &str) -> bool {
+        let contained = self.contains(panel);
+        self.root = self.root.take().and_then(|root| root.remove(panel));
+        self.floating.retain(|floating| floating.panel != panel);
+        contained
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<DockLayout> {
+        serde_json::from_str(json)
+    }
+
+    /// Dock the panel as the only one, if no panel is docked yet.
+    fn dock_first(&mut self, panel: &str) {
+        if self.root.is_none() {
+            self.remove(panel);
+            self.root = Some(DockNode::tabs(vec![panel]));
+        }
+    }
+}
+
+/// A workspace of panels that are arranged in tabs and splits according to a
+/// `DockLayout`. Panels can be dragged by their tabs onto the edges or the
+/// center of other panels, or out of the workspace into floating windows.
+pub struct DockSpace {
+    placement: Placement,
+}
+
+impl Default for DockSpace {
+    fn default() -> Self {
+        DockSpace::new()
+    }
+}
+
+impl DockSpace {
+    pub fn new() -> Self {
+        DockSpace {
+            placement: Placement {
+                width: Sizing::Fill,
+                height: Sizing::Fill,
+                .. Placement::default()
+            },
+        }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+
+    /// Show the workspace, calling `build` with the name of each visible panel
+    /// in order to create its content.
+    pub fn reify<R, F>(self, id: Id, frame: &mut GluiFrame<R>, layout: &mut DockLayout, mut build: F) where
+        R: Renderer,
+        F: FnMut(&mut GluiFrame<R>, &str)
+    {
+        let id = frame.scoped_id(id);
+        let region = frame.place(self.placement, SizeHint::default());
+        let divider = frame.style().splitter_width;
+
+        // Dividers are handled first, such that dragging them takes effect immediately
+        let (_, dividers) = arrange(layout, region, divider);
+        let mouse = frame.uistate().mouse_pos;
+        let mut resizing = false;
+        for (path, handle, orientation, parent) in dividers {
+            let divider_id = id.with(&("divider", &path));
+            if ! frame.interact(divider_id, handle, Sense::click()).pressed {
+                continue;
+            }
+
+            let (along, extent) = match orientation {
+                Orientation::Horizontal => (mouse.x, parent.w - divider),
+                Orientation::Vertical => (mouse.y, parent.h - divider),
+            };
+            if let Some(DockNode::Split { ratio, .. }) = layout.root.as_mut().and_then(|root| root.node_mut(&path)) {
+                let (_, start, start_ratio) = layout.resize.get_or_insert((path.clone(), along, *ratio)).clone();
+                let limit = if extent > 2.0 * MIN_PANEL_SIZE { MIN_PANEL_SIZE / extent } else { 0.5 };
+                *ratio = (start_ratio + (along - start) / extent.max(1.0)).min(1.0 - limit).max(limit);
+                resizing = true;
+            }
+        }
+        if ! resizing {
+            layout.resize = None;
+        }
+
+        let (groups, dividers) = arrange(layout, region, divider);
+        for (path, handle, _, _) in dividers {
+            let divider_id = id.with(&("divider", &path));
+            let uistate = frame.uistate();
+            let color = if uistate.is_active(divider_id) || uistate.is_hover(divider_id) {
+                frame.style().splitter_active
+            } else {
+                frame.style().splitter_color
+            };
+            frame.fill_rect(handle, 0.0, color);
+        }
+
+        let mut targets = Vec::new();
+        for (path, rect) in groups {
+            let (panels, selected) = match layout.root.as_ref().and_then(|root| root.node(&path)).cloned() {
+                Some(DockNode::Tabs { panels, selected }) => (panels, selected),
+                _ => continue,
+            };
+            let (clicked, content) = tab_bar(frame, id, layout, &panels, selected, rect);
+            if let Some(index) = clicked {
+                if let Some(DockNode::Tabs { selected, .. }) = layout.root.as_mut().and_then(|root| root.node_mut(&path)) {
+                    *selected = index;
+                }
+            }
+            if let Some(panel) = panels.get(clicked.unwrap_or(selected)) {
+                panel_content(frame, id, panel, content, &mut build);
+            }
+            targets.push((rect, panels));
+        }
+
+        for floating in layout.floating.clone() {
+            let window_id = id.with(&("floating", &floating.panel));
+            let window = Window::new(floating.panel.as_str())
+                .region(floating.region)
+                .content(Layout::column())
+                .closable(false);
+            frame.window(window_id, &window, &mut true, |frame| {
+                let rect = frame.available_region();
+                let panels = [floating.panel.clone()];
+                let (_, content) = tab_bar(frame, id, layout, &panels, 0, rect);
+                panel_content(frame, id, &floating.panel, content, &mut build);
+            });
+
+            // Keep the position the window was moved to
+            if let Some(rect) = frame.window_rect(window_id) {
+                let panel = layout.floating.iter_mut().find(|panel| panel.panel == floating.panel);
+                if let Some(panel) = panel {
+                    panel.region = rect;
+                }
+            }
+        }
+
+        drag_tab(frame, id, layout, region, &targets);
+    }
+}
+
+/// The smallest extent of a docked panel when dragging a divider.
+const MIN_PANEL_SIZE: f64 = 40.0;
+
+/// The distance the mouse must move while holding a tab before it is dragged.
+const DRAG_THRESHOLD: f64 = 4.0;
+
+/// The fraction of a panel near each edge in which dropping a tab docks it to that side.
+const EDGE_ZONE: f64 = 0.25;
+
+/// The size of a floating window created by dragging a tab out of the workspace.
+const FLOATING_SIZE: Size = Size { w: 300.0, h: 200.0 };
+
+/// A split divider as its path, its region, the orientation and the region of the split.
+type Divider = (Vec<usize>, Rect, Orientation, Rect);
+
+/// Compute the regions of all groups of tabs and of all dividers in the layout.
+fn arrange(layout: &DockLayout, region: Rect, divider: f64) -> (Vec<(Vec<usize>, Rect)>, Vec<Divider>) {
+    fn visit(node: &DockNode, rect: Rect, divider: f64, path: &mut Vec<usize>, groups: &mut Vec<(Vec<usize>, Rect)>, dividers: &mut Vec<Divider>) {
+        match node {
+            DockNode::Tabs { .. } => groups.push((path.clone(), rect)),
+            DockNode::Split { orientation, ratio, first, second } => {
+                let (first_rect, handle, second_rect) = match orientation {
+                    Orientation::Horizontal => {
+                        let w = ((rect.w - divider) * ratio).max(0.0);
+                        (
+                            Rect { w, .. rect },
+                            Rect { x: rect.x + w, w: divider, .. rect },
+                            Rect { x: rect.x + w + divider, w: (rect.w - w - divider).max(0.0), .. rect },
+                        )
+                    }
+                    Orientation::Vertical => {
+                        let h = ((rect.h - divider) * ratio).max(0.0);
+                        (
+                            Rect { h, .. rect },
+                            Rect { y: rect.y + h, h: divider, .. rect },
+                            Rect { y: rect.y + h + divider, h: (rect.h - h - divider).max(0.0), .. rect },
+                        )
+                    }
+                };
+                dividers.push((path.clone(), handle, *orientation, rect));
+                path.push(0);
+                visit(first, first_rect, divider, path, groups, dividers);
+                path.pop();
+                path.push(1);
+                visit(second, second_rect, divider, path, groups, dividers);
+                path.pop();
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    let mut dividers = Vec::new();
+    if let Some(root) = &layout.root {
+        visit(root, region, divider, &mut Vec::new(), &mut groups, &mut dividers);
+    }
+    (groups, dividers)
+}
+
+/// Draw the tabs of a group of panels at the top of the region and handle
+/// selecting and dragging them. Returns the index of the tab that was pressed,
+/// if any, and the region below the tabs.
+fn tab_bar<R: Renderer>(frame: &mut GluiFrame<R>, id: Id, layout: &mut DockLayout, panels: &[String], selected: usize, region: Rect) -> (Option<usize>, Rect) {
+    let style = frame.style();
+    let height = style.tab_height.min(region.h);
+    let padding = style.widget_padding;
+    let bar_color = style.window_title;
+    let selected_color = style.window_background;
+    let hover_color = style.clickable_hover;
+    let text_color = style.foreground_color;
+    let border_color = style.widget_border.color;
+
+    let bar = Rect { h: height, .. region };
+    frame.fill_rect(bar, 0.0, bar_color);
+
+    let mouse = frame.uistate().mouse_pos;
+    let mut pressed = None;
+    let mut x = bar.x;
+    for (index, panel) in panels.iter().enumerate() {
+        let text_bounds = frame.text_measure(panel);
+        let tab = Rect { x, w: text_bounds.w + 2.0 * padding, .. bar };
+        x += tab.w;
+
+        let tab_id = id.with(&("tab", panel));
+        let response = frame.interact(tab_id, tab, Sense::click());
+        let hover = response.hovered;
+        if response.pressed {
+            pressed = Some(index);
+            let drag = layout.drag.get_or_insert_with(|| TabDrag { panel: panel.clone(), start: mouse, moved: false });
+            if (mouse.x - drag.start.x).abs() > DRAG_THRESHOLD || (mouse.y - drag.start.y).abs() > DRAG_THRESHOLD {
+                drag.moved = true;
+            }
+        }
+
+        let color = if pressed.unwrap_or(selected) == index {
+            selected_color
+        } else if hover {
+            hover_color
+        } else {
+            bar_color
+        };
+        frame.fill_rect(tab, 0.0, color);
+        let position = Point { x: tab.x + padding, y: tab.center(text_bounds).y };
+        frame.clipped(tab.intersect(&bar), |frame| frame.text_render(panel, position, text_color));
+    }
+
+    frame.stroke_rect(region, 0.0, 1.0, border_color);
+    let content = Rect { y: region.y + height, h: region.h - height, .. region };
+    (pressed, content)
+}
+
+/// Show the content of a panel in a scroll area filling the region.
+fn panel_content<R, F>(frame: &mut GluiFrame<R>, id: Id, panel: &str, region: Rect, build: &mut F) where
+    R: Renderer,
+    F: FnMut(&mut GluiFrame<R>, &str)
+{
+    let padding = frame.style().widget_padding;
+    let area = ScrollArea::new()
+        .content(Layout::column().padding(padding).spacing(padding))
+        .region(region);
+    // Panels are scoped by their names, such that they can use the same widget IDs
+    frame.scroll_area(id.with(&("panel", panel)), &area, |frame| {
+        frame.with_id(panel, |frame| build(frame, panel))
+    });
+}
+
+/// Where a dragged tab is dropped.
+enum Drop {
+    /// Next to the tabs containing the target panel.
+    Dock(String, DockPosition, Rect),
+    /// As the only docked panel.
+    First(Rect),
+    /// Into a floating window.
+    Float(Rect),
+}
+
+/// Show where the tab being dragged would be dropped, and move its panel there
+/// once the mouse button is released.
+fn drag_tab<R: Renderer>(frame: &mut GluiFrame<R>, id: Id, layout: &mut DockLayout, region: Rect, targets: &[(Rect, Vec<String>)]) {
+    let drag = match layout.drag.clone() {
+        Some(drag) => drag,
+        None => return,
+    };
+    let uistate = frame.uistate();
+    let released = uistate.mouse_released(PointerButton::Left);
+    if ! uistate.is_active(id.with(&("tab", &drag.panel))) {
+        layout.drag = None;
+        return;
+    }
+    if ! drag.moved {
+        if released {
+            layout.drag = None;
+        }
+        return;
+    }
+
+    let mouse = uistate.mouse_pos;
+    let drop = drop_target(layout, &drag.panel, region, targets, mouse, frame.style().tab_height);
+    if released {
+        match drop {
+            Some(Drop::Dock(target, position, _)) => {
+                layout.dock(&drag.panel, &target, position);
+            }
+            Some(Drop::First(_)) => layout.dock_first(&drag.panel),
+            Some(Drop::Float(rect)) => layout.undock(&drag.panel, rect),
+            None => {},
+        }
+        layout.drag = None;
+    } else if let Some(Drop::Dock(_, _, rect)) | Some(Drop::First(rect)) | Some(Drop::Float(rect)) = drop {
+        let color = frame.style().dock_preview;
+        frame.with_layer(Layer::Popup, |frame| frame.fill_rect(rect, 0.0, color));
+    }
+}
+
+fn drop_target(layout: &DockLayout, panel: &str, region: Rect, targets: &[(Rect, Vec<String>)], mouse: Point, tab_height: f64) -> Option<Drop> {
+    if let Some((rect, panels)) = targets.iter().find(|(rect, _)| rect.contains(mouse)) {
+        // Dropping a panel onto itself does not change anything
+        let target = panels.iter().find(|name| *name != panel)?;
+        let position = drop_position(*rect, mouse, tab_height);
+        let (x, y, w, h) = (rect.x, rect.y, rect.w, rect.h);
+        let preview = match position {
+            DockPosition::Left => Rect { w: w / 2.0, .. *rect },
+            DockPosition::Right => Rect { x: x + w / 2.0, w: w / 2.0, .. *rect },
+            DockPosition::Top => Rect { h: h / 2.0, .. *rect },
+            DockPosition::Bottom => Rect { y: y + h / 2.0, h: h / 2.0, .. *rect },
+            DockPosition::Center => Rect { x, y, w, h },
+        };
+        return Some(Drop::Dock(target.clone(), position, preview));
+    }
+
+    if layout.root.is_none() && region.contains(mouse) {
+        Some(Drop::First(region))
+    } else if layout.floating.iter().any(|floating| floating.panel == panel) {
+        // Floating panels are moved by their title bar instead
+        None
+    } else {
+        let position = Point { x: mouse.x - FLOATING_SIZE.w / 2.0, y: mouse.y - tab_height / 2.0 };
+        Some(Drop::Float(Rect::with_position_size(&position, &FLOATING_SIZE)))
+    }
+}
+
+/// The side of the region closest to the mouse, or the center if the mouse is
+/// far from all edges or above the tabs.
+fn drop_position(region: Rect, mouse: Point, tab_height: f64) -> DockPosition {
+    if mouse.y < region.y + tab_height {
+        return DockPosition::Center;
+    }
+    let x = (mouse.x - region.x) / region.w.max(1.0);
+    let y = (mouse.y - region.y) / region.h.max(1.0);
+    let edges = [
+        (x, DockPosition::Left),
+        (1.0 - x, DockPosition::Right),
+        (y, DockPosition::Top),
+        (1.0 - y, DockPosition::Bottom),
+    ];
+    edges.iter()
+        .filter(|(distance, _)| *distance < EDGE_ZONE)
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map_or(DockPosition::Center, |(_, position)| *position)
+}
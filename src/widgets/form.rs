@@ -0,0 +1,68 @@
+use crate::core::{Grid, Track, Sizing, Id, Rect, GluiFrame, Renderer};
+use super::Label;
+
+/// A grid of label/field pairs, where the labels share a column that is as wide
+/// as the widest label and the fields fill the remaining width.
+///
+/// Add the rows with `form_field` inside the callback passed to `reify`.
+pub struct Form {
+    grid: Grid,
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Form::new()
+    }
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Form {
+            grid: Grid::new(vec![Track::Auto, Track::Fill]),
+        }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.grid = self.grid.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.grid = self.grid.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.grid = self.grid.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.grid = self.grid.width(width);
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.grid = self.grid.height(height);
+        self
+    }
+
+    pub fn reify<R, T, F>(self, id: Id, frame: &mut GluiFrame<R>, build: F) -> T where
+        R: Renderer,
+        F: FnOnce(&mut GluiFrame<R>) -> T
+    {
+        let padding = frame.style().widget_padding;
+        let grid = self.grid.spacing(2.0 * padding, padding);
+        frame.grid(id, &grid, build)
+    }
+}
+
+/// Add a row with a label and the widget created by `field` to the enclosing form.
+pub fn form_field<R, S, T, F>(frame: &mut GluiFrame<R>, label: S, field: F) -> T where
+    R: Renderer,
+    S: AsRef<str>,
+    F: FnOnce(&mut GluiFrame<R>) -> T
+{
+    Label::new(label).reify(frame);
+    field(frame)
+}
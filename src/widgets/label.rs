@@ -0,0 +1,66 @@
+use crate::core::{Placement, Sizing, SizeHint, Rect, GluiFrame, Renderer, Response, Widget};
+
+/// A non-interactive piece of text.
+pub struct Label<S> {
+    text: S,
+    placement: Placement,
+}
+
+impl<S> Label<S> where
+    S: AsRef<str>
+{
+    pub fn new(text: S) -> Label<S> {
+        Label {
+            text,
+            placement: Placement::default(),
+        }
+    }
+
+    pub fn at(mut self, x: f64, y: f64) -> Self {
+        self.placement = self.placement.at(x, y);
+        self
+    }
+
+    pub fn size(mut self, w: f64, h: f64) -> Self {
+        self.placement = self.placement.size(w, h);
+        self
+    }
+
+    pub fn region(mut self, region: Rect) -> Self {
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+
+    pub fn reify<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
+        self.ui(frame)
+    }
+}
+
+impl<S> Widget for Label<S> where
+    S: AsRef<str>
+{
+    fn ui<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
+        let text_bounds = frame.text_measure(self.text.as_ref());
+        let region = frame.place(self.placement, SizeHint::at_least(text_bounds));
+
+        // Left aligned and vertically centered, such that labels line up
+        // with the text of the widgets next to them
+        let text_rect = Rect {
+            x: region.x,
+            .. region.center(text_bounds)
+        };
+        let text_color = frame.style().foreground_color;
+        frame.text_render(self.text.as_ref(), text_rect.top_left(), text_color);
+        frame.hover(region)
+    }
+}
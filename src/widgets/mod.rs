@@ -1,7 +1,15 @@
 mod button;
+mod dialog;
+mod dock;
+mod form;
+mod label;
 mod progress_bar;
 mod text_box;
 
 pub use button::Button;
+pub use dialog::{Dialog, DialogResult, Confirmation, message_box, confirm};
+pub use dock::{DockLayout, DockNode, DockPosition, DockSpace, FloatingPanel};
+pub use form::{Form, form_field};
+pub use label::Label;
 pub use progress_bar::ProgressBar;
-pub use text_box::{TextBox, TextBoxState};
\ No newline at end of file
+pub use text_box::{TextBox, TextBoxState};
@@ -1,11 +1,11 @@
-use crate::core::{Rect, GluiFrame};
+use crate::core::{Placement, Sizing, SizeHint, Rect, Size, Color, GluiFrame, Renderer, Response, Widget};
 
 pub struct ProgressBar<S> {
     label: S,
     progress: f32,
     min_value: f32,
     max_value: f32,
-    region: Rect,
+    placement: Placement,
 }
 
 impl ProgressBar<&'static str> {
@@ -14,26 +14,34 @@ impl ProgressBar<&'static str> {
     }
 }
 
+impl Default for ProgressBar<&'static str> {
+    fn default() -> Self {
+        ProgressBar::new()
+    }
+}
+
 impl<S> ProgressBar<S> where
     S: AsRef<str>
 {
+    #[allow(clippy::redundant_field_names)]
     pub fn with_label(label: S) -> ProgressBar<S> {
         ProgressBar {
             label: label,
             progress: 0.0,
             min_value: 0.0,
             max_value: 100.0,
-            region: Rect::zero(),
+            placement: Placement::default(),
         }
     }
 
+    #[allow(clippy::redundant_field_names)]
     pub fn label<S2: AsRef<str>>(self, label: S2) -> ProgressBar<S2> {
         ProgressBar {
             label: label,
             progress: self.progress,
             min_value: self.min_value,
             max_value: self.max_value,
-            region: self.region,
+            placement: self.placement,
         }
     }
 
@@ -45,6 +53,7 @@ impl<S> ProgressBar<S> where
         }
     }
 
+    #[allow(clippy::redundant_field_names)]
     pub fn progress(self, progress: f32) -> Self {
         ProgressBar {
             progress: progress,
@@ -53,23 +62,44 @@ impl<S> ProgressBar<S> where
     }
     
     pub fn at(mut self, x: f64, y: f64) -> Self {
-        self.region = Rect { x: x, y: y, .. self.region };
+        self.placement = self.placement.at(x, y);
         self
     }
     
     pub fn size(mut self, w: f64, h: f64) -> Self {
-        self.region = Rect { w: w, h: h, .. self.region };
+        self.placement = self.placement.size(w, h);
         self
     }
 
     pub fn region(mut self, region: Rect) -> Self {
-        self.region = region;
+        self.placement = self.placement.region(region);
+        self
+    }
+
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
         self
     }
 
-    pub fn reify(self, frame: &mut GluiFrame) {
+    pub fn reify<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
+        self.ui(frame)
+    }
+}
+
+impl<S> Widget for ProgressBar<S> where
+    S: AsRef<str>
+{
+    fn ui<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
         let text_bounds = frame.text_measure(self.label.as_ref());
-        let text_rect = self.region.center(text_bounds);
+        let padding = frame.style().widget_padding;
+        let natural = Size { w: text_bounds.w + 2.0 * padding, h: text_bounds.h + 2.0 * padding };
+        let region = frame.place(self.placement, SizeHint::at_least(natural));
+        let text_rect = region.center(text_bounds);
 
         let mut progress = (self.progress - self.min_value) / (self.max_value - self.min_value);
 
@@ -77,41 +107,24 @@ impl<S> ProgressBar<S> where
             progress = 0.0;
         }
 
-        let progress_offset = self.region.w as f32 * progress.min(1.).max(0.);
-
-        let progress_clip = nanovg::Clip::Scissor(nanovg::Scissor {
-            x: self.region.x as f32,
-            y: self.region.y as f32,
-            width: progress_offset,
-            height: self.region.h as f32,
-            transform: None,
-        });
+        let progress_offset = region.w * f64::from(progress.clamp(0., 1.));
 
-        let border = &frame.style().widget_border;
+        let progress_clip = Rect {
+            w: progress_offset,
+            .. region
+        };
 
-        frame.nanovg().path(|path| {
-            path.rounded_rect(
-                (self.region.x as f32, self.region.y as f32),
-                (self.region.w as f32, self.region.h as f32),
-                border.radius,
-            );            
+        let border_radius = frame.style().widget_border.radius;
+        let border_color = frame.style().widget_border.color;
+        let text_color = frame.style().foreground_color;
 
-            path.fill(nanovg::Color::from_rgb(128, 255, 128), nanovg::FillOptions::default());
-        }, nanovg::PathOptions {
-            clip: progress_clip,
-            .. nanovg::PathOptions::default()
+        frame.clipped(progress_clip, |frame| {
+            frame.fill_rect(region, border_radius, Color::from_rgb(128, 255, 128));
         });
 
-        frame.nanovg().path(|path| {
-            path.rounded_rect(
-                (self.region.x as f32, self.region.y as f32),
-                (self.region.w as f32, self.region.h as f32),
-                border.radius,
-            );            
-
-            path.stroke(border.color, nanovg::StrokeOptions::default());
-        }, nanovg::PathOptions::default());
+        frame.stroke_rect(region, border_radius, 1.0, border_color);
 
-        frame.text_render(self.label.as_ref(), text_rect.top_left(), frame.style().foreground_color);
+        frame.text_render(self.label.as_ref(), text_rect.top_left(), text_color);
+        frame.hover(region)
     }
 }
@@ -1,10 +1,11 @@
-use crate::core::{Id, Rect, GluiFrame, VirtualKeyCode};
-use nanovg::{PathOptions, StrokeOptions};
+use crate::core::{Placement, Sizing, SizeHint, Id, Point, Rect, Size, GluiFrame, Renderer, Response, Sense, Widget, Key};
 
 pub struct TextBox<'a, S> {
     hint: S,
-    region: Rect,
-    state: &'a mut TextBoxState,
+    id: Option<Id>,
+    placement: Placement,
+    /// The state kept by the application, if any.
+    state: Option<&'a mut TextBoxState>,
 }
 
 pub struct TextBoxState  {
@@ -21,6 +22,7 @@ impl TextBoxState {
         }
     }
 
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
         self.text.iter().collect()
     }
@@ -59,12 +61,29 @@ impl TextBoxState {
     }
 }
 
+impl Default for TextBoxState {
+    fn default() -> Self {
+        TextBoxState::new()
+    }
+}
+
 impl<'a> TextBox<'a, &'static str> {
     pub fn new(state: &'a mut TextBoxState) -> Self {
         TextBox {
             hint: "",
-            region: Rect::zero(),
-            state: state,
+            id: None,
+            placement: Placement::default(),
+            state: Some(state),
+        }
+    }
+
+    /// A text box whose state is kept by glui under its ID, see `GluiFrame::state`.
+    pub fn managed() -> Self {
+        TextBox {
+            hint: "",
+            id: None,
+            placement: Placement::default(),
+            state: None,
         }
     }
 }
@@ -72,105 +91,149 @@ impl<'a> TextBox<'a, &'static str> {
 impl<'a, S> TextBox<'a, S> where
     S: AsRef<str>
 {
+    #[allow(clippy::redundant_field_names)]
     pub fn hint<S2: AsRef<str>>(self, hint: S2) -> TextBox<'a, S2> {
         TextBox {
             hint: hint,
-            region: self.region,
+            id: self.id,
+            placement: self.placement,
             state: self.state,
         }
     }
     
+    /// The ID of the text box, which is derived from its hint unless given.
+    pub fn id(self, id: Id) -> Self {
+        TextBox { id: Some(id), .. self }
+    }
+
     pub fn at(mut self, x: f64, y: f64) -> Self {
-        self.region = Rect { x: x, y: y, .. self.region };
+        self.placement = self.placement.at(x, y);
         self
     }
     
     pub fn size(mut self, w: f64, h: f64) -> Self {
-        self.region = Rect { w: w, h: h, .. self.region };
+        self.placement = self.placement.size(w, h);
         self
     }
 
     pub fn region(mut self, region: Rect) -> Self {
-        self.region = region;
+        self.placement = self.placement.region(region);
         self
     }
 
-    pub fn reify(self, id: Id, frame: &mut GluiFrame) -> bool {
-        let clicked = frame.clickable_widget(id, self.region);
-        let focused = frame.focusable_widget(id, clicked, |key_event| {
+    pub fn width(mut self, width: Sizing) -> Self {
+        self.placement.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Sizing) -> Self {
+        self.placement.height = height;
+        self
+    }
+
+    /// Show the text box with the given ID, see `Widget::ui`.
+    pub fn reify<R: Renderer>(self, id: Id, frame: &mut GluiFrame<R>) -> Response {
+        self.id(id).ui(frame)
+    }
+
+    fn is_input_char_valid(ch: char) -> bool {
+        ! (ch == '\r' || ch == '\n' || ch == '\t')
+    }
+
+    /// Show a text box editing the given state.
+    fn show<R: Renderer>(id: Id, hint: &str, placement: Placement, state: &mut TextBoxState, frame: &mut GluiFrame<R>) -> Response {
+        let hint_bounds = frame.text_measure(hint);
+        let text_bounds = frame.text_measure(state.to_string().as_str());
+        let padding = frame.style().widget_padding;
+        let natural = Size {
+            w: hint_bounds.w.max(text_bounds.w) + 2.0 * padding,
+            h: hint_bounds.h.max(text_bounds.h) + 2.0 * padding,
+        };
+        // Text boxes may be narrower than their content, but not lower
+        let size_hint = SizeHint::flexible(natural)
+            .min_width(frame.style().text_box_min_width)
+            .min_height(natural.h);
+        let region = frame.place(placement, size_hint);
+
+        let mut response = frame.interact_with_keys(id, region, Sense::focus(), |key_event| {
             if key_event.state.is_pressed() {
                 match key_event.key {
-                    VirtualKeyCode::Left => self.state.left(),
-                    VirtualKeyCode::Right => self.state.right(),
+                    Key::Left => state.left(),
+                    Key::Right => state.right(),
                     _ => {},
                 }
             }
             true
         });
-
-        let mut changed = false;
+        let focused = response.has_focus;
 
         if focused {
             for ch in frame.uistate_mut().text_input.drain(..) {
-                println!("{:?}", ch);
                 if ch == '\u{8}' { // Backspace
-                    self.state.backspace();
-                    changed = true;
+                    state.backspace();
+                    response.changed = true;
                 } else if Self::is_input_char_valid(ch) {
-                    self.state.insert(ch);
-                    changed = true;
+                    state.insert(ch);
+                    response.changed = true;
                 }
             }
         }
 
-        frame.nanovg().path(|path| {
-            let border = &frame.style().widget_border;
-
-            path.rounded_rect(
-                (self.region.x as f32, self.region.y as f32),
-                (self.region.w as f32, self.region.h as f32),
-                border.radius,
-            );
+        let border_radius = frame.style().widget_border.radius;
+        let border_color = frame.style().widget_border.color;
+        let hint_color = frame.style().hint_color;
+        let text_color = frame.style().foreground_color;
 
-            path.stroke(border.color, Default::default());
-        }, PathOptions::default());
+        frame.stroke_rect(region, border_radius, 1.0, border_color);
 
-        if ! focused && self.state.text.is_empty() {
-            let hint_bounds = frame.text_measure(self.hint.as_ref());
+        if ! focused && state.text.is_empty() {
             let hint_rect = Rect {
-                x: self.region.x + 5.,
-                .. self.region.center(hint_bounds)
+                x: region.x + padding,
+                .. region.center(hint_bounds)
             };
-            frame.text_render(self.hint.as_ref(), hint_rect.top_left(), frame.style().hint_color);
+            frame.text_render(hint, hint_rect.top_left(), hint_color);
         }
 
-        let text = self.state.to_string();
+        let text = state.to_string();
 
         let text_bounds = frame.text_measure(text.as_str());
         let text_rect = Rect {
-            x: self.region.x + 5.,
-            .. self.region.center(text_bounds)
+            x: region.x + padding,
+            .. region.center(text_bounds)
         };
-        frame.text_render(text.as_str(), text_rect.top_left(), frame.style().foreground_color);
+        frame.text_render(text.as_str(), text_rect.top_left(), text_color);
 
         if focused {
-            let cursor_x = frame.nanovg().text_glyph_positions(text_rect.top_left().as_f32(), text)
-                .nth(self.state.cursor)
-                .map(|glyph| glyph.x)
-                .unwrap_or((text_rect.x + text_rect.w) as f32);
-            
+            let cursor_x = frame.text_glyph_positions(text.as_str(), text_rect.top_left())
+                .get(state.cursor)
+                .cloned()
+                .unwrap_or(text_rect.x + text_rect.w);
+
             // draw cursor when focused
-            frame.nanovg().path(|path| {
-                path.move_to((cursor_x, text_rect.y as f32));
-                path.line_to((cursor_x, (text_rect.y + text_rect.h) as f32));
-                path.stroke(frame.style().foreground_color, StrokeOptions::default());
-            }, PathOptions::default());
+            frame.stroke_line(
+                Point { x: cursor_x, y: text_rect.y },
+                Point { x: cursor_x, y: text_rect.y + text_rect.h },
+                1.0,
+                text_color,
+            );
         }
 
-        changed
+        response
     }
+}
 
-    fn is_input_char_valid(ch: char) -> bool {
-        ! (ch == '\r' || ch == '\n' || ch == '\t')
+impl<'a, S> Widget for TextBox<'a, S> where
+    S: AsRef<str>
+{
+    /// Show the text box. The response is marked as changed when the text was edited.
+    fn ui<R: Renderer>(self, frame: &mut GluiFrame<R>) -> Response {
+        let id = self.id.unwrap_or_else(|| Id::from_key(self.hint.as_ref()));
+        let id = frame.scoped_id(id);
+        let (hint, placement) = (self.hint.as_ref(), self.placement);
+
+        match self.state {
+            Some(state) => Self::show(id, hint, placement, state, frame),
+            None => frame.with_state(id, |frame, state| Self::show(id, hint, placement, state, frame)),
+        }
     }
 }
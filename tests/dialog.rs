@@ -0,0 +1,140 @@
+use glui::core::{GluiFrame, Id, Key, Layout, Modifiers, Renderer};
+use glui::testing::Harness;
+use glui::widgets::{message_box, Button, Dialog, DialogResult};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// A button at (10, 10) that is 150 by 24 units large, and a dialog with
+/// three buttons in front of it while it is open.
+struct App {
+    open: bool,
+    behind_clicks: usize,
+    result: Option<DialogResult>,
+}
+
+/// The IDs of the widgets of the app as seen by the `GluiState`.
+struct AppIds {
+    behind: Id,
+    buttons: Vec<Id>,
+}
+
+impl App {
+    fn new() -> Self {
+        App { open: false, behind_clicks: 0, result: None }
+    }
+
+    fn ui<R: Renderer>(&mut self, frame: &mut GluiFrame<R>) -> AppIds {
+        let behind = Id::from_key("behind");
+        let dialog = Id::from_key("dialog");
+
+        frame.layout(Layout::column().padding(10.0), |frame| {
+            if Button::with_label("Behind").size(150.0, 24.0).reify(behind, frame).clicked() {
+                self.behind_clicks += 1;
+            }
+        });
+        let result = Dialog::new("Save changes?")
+            .buttons(vec!["Save", "Discard", "Cancel"])
+            .reify(dialog, frame, &mut self.open, |_| ());
+        self.result = self.result.or(result);
+
+        AppIds {
+            behind: frame.scoped_id(behind),
+            buttons: (0 .. 3usize).map(|index| frame.scoped_id(dialog.with(&index))).collect(),
+        }
+    }
+}
+
+#[test]
+fn focus_moves_into_opened_modal() {
+    let mut harness = harness();
+    let mut app = App::new();
+    let output = harness.frame(|frame| app.ui(frame));
+    assert_eq!(output.focus, Some(output.value.behind));
+
+    app.open = true;
+    harness.frame(|frame| app.ui(frame));
+    let output = harness.frame(|frame| app.ui(frame));
+    assert_eq!(output.focus, Some(output.value.buttons[0]));
+}
+
+#[test]
+fn tab_wraps_within_modal() {
+    let mut harness = harness();
+    let mut app = App { open: true, .. App::new() };
+    harness.frame(|frame| app.ui(frame));
+
+    let mut focus_after = |harness: &mut Harness, modifiers| {
+        harness.key_press(Key::Tab, modifiers);
+        harness.frame(|frame| app.ui(frame));
+        let output = harness.frame(|frame| app.ui(frame));
+        output.value.buttons.iter().position(|id| output.focus == Some(*id))
+    };
+    assert_eq!(focus_after(&mut harness, Modifiers::NONE), Some(1));
+    assert_eq!(focus_after(&mut harness, Modifiers::NONE), Some(2));
+    assert_eq!(focus_after(&mut harness, Modifiers::NONE), Some(0));
+    assert_eq!(focus_after(&mut harness, Modifiers::SHIFT), Some(2));
+    assert_eq!(focus_after(&mut harness, Modifiers::SHIFT), Some(1));
+}
+
+#[test]
+fn escape_dismisses_modal() {
+    let mut harness = harness();
+    let mut app = App { open: true, .. App::new() };
+    harness.frame(|frame| app.ui(frame));
+
+    harness.key_press(Key::Escape, Modifiers::NONE);
+    harness.frame(|frame| app.ui(frame));
+    assert!(! app.open);
+    assert_eq!(app.result, Some(DialogResult::Dismissed));
+
+    // Afterwards the focus can move to the widgets behind it again
+    harness.frame(|frame| app.ui(frame));
+    let output = harness.frame(|frame| app.ui(frame));
+    assert_eq!(output.focus, Some(output.value.behind));
+}
+
+#[test]
+fn modal_blocks_clicks_behind_it() {
+    let mut harness = harness();
+    let mut app = App { open: true, .. App::new() };
+    harness.frame(|frame| app.ui(frame));
+
+    let output = harness.click(50.0, 20.0, |frame| app.ui(frame));
+    assert_eq!(output.hover, None);
+    assert_eq!(app.behind_clicks, 0);
+    assert!(app.open);
+
+    // Once it is closed, the widgets behind it are clicked again
+    app.open = false;
+    harness.frame(|frame| app.ui(frame));
+    harness.click(50.0, 20.0, |frame| app.ui(frame));
+    assert_eq!(app.behind_clicks, 1);
+}
+
+#[test]
+fn message_box_reports_how_it_was_closed() {
+    let mut harness = harness();
+    let mut open = true;
+    let message = |frame: &mut GluiFrame<_>, open: &mut bool| {
+        message_box(Id::from_key("message"), frame, open, "Saved", "The file was saved.")
+    };
+    harness.frame(|frame| message(frame, &mut open));
+
+    harness.key_press(Key::Enter, Modifiers::NONE);
+    let output = harness.frame(|frame| message(frame, &mut open));
+    assert_eq!(output.value, Some(DialogResult::Button(0)));
+    assert!(! open);
+
+    open = true;
+    harness.frame(|frame| message(frame, &mut open));
+    harness.frame(|frame| message(frame, &mut open));
+    harness.key_press(Key::Escape, Modifiers::NONE);
+    let output = harness.frame(|frame| message(frame, &mut open));
+    assert_eq!(output.value, Some(DialogResult::Dismissed));
+}
@@ -0,0 +1,98 @@
+use glui::core::{Color, DisplayList, DrawCommand, GluiFrame, Id, Layout, Point, Rect, Renderer};
+use glui::testing::Harness;
+use glui::widgets::Button;
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// Three buttons at (10, 10), (10, 38) and (10, 66), each 150 by 24 units large.
+fn buttons<R: Renderer>(frame: &mut GluiFrame<R>) {
+    frame.layout(Layout::column().padding(10.0).spacing(4.0), |frame| {
+        for label in &["Increment", "Decrement", "Reset"] {
+            Button::with_label(*label).size(150.0, 24.0).reify(Id::from_key(label), frame);
+        }
+    });
+}
+
+fn fill(x: f64) -> DrawCommand {
+    DrawCommand::FillRect {
+        rect: Rect { x, y: 0.0, w: 10.0, h: 10.0 },
+        radius: 0.0,
+        color: Color::from_rgb(255, 0, 0),
+    }
+}
+
+fn list(commands: Vec<DrawCommand>) -> DisplayList {
+    let mut list = DisplayList::new();
+    for command in commands {
+        list.push(command);
+    }
+    list
+}
+
+#[test]
+fn find_text_locates_labels() {
+    let mut harness = harness();
+    let output = harness.frame(buttons);
+
+    // The label is drawn within the region of its button
+    let reset = Rect { x: 10.0, y: 66.0, w: 150.0, h: 24.0 };
+    let position = output.display_list.find_text("Reset").unwrap();
+    assert!(reset.contains(position), "Reset button drawn at {:?}", position);
+    assert!(position.x > reset.x && position.y > reset.y);
+    assert!(output.display_list.find_text("Decrement").unwrap().y < position.y);
+
+    assert_eq!(output.display_list.find_text("Res"), None);
+}
+
+#[test]
+fn json_round_trip() {
+    let mut harness = harness();
+    let mut list = harness.frame(buttons).display_list;
+    list.push(DrawCommand::StrokeLine {
+        from: Point { x: 1.0, y: 2.0 },
+        to: Point { x: 3.5, y: 4.0 },
+        width: 1.5,
+        color: Color::from_rgb(1, 2, 3),
+    });
+    assert!(! list.is_empty());
+
+    let json = list.to_json().unwrap();
+    assert!(json.contains("\"Reset\""));
+    assert_eq!(DisplayList::from_json(&json).unwrap(), list);
+    assert!(DisplayList::from_json("[{\"Unknown\": {}}]").is_err());
+}
+
+#[test]
+fn first_difference_between_frames() {
+    let mut harness = harness();
+    let first = harness.frame(buttons).display_list;
+    assert_eq!(first.first_difference(&harness.frame(buttons).display_list), None);
+
+    // Hovering the second button changes its background, but not the commands before it
+    harness.mouse_move(50.0, 50.0);
+    harness.frame(buttons);
+    let hovered = harness.frame(buttons).display_list;
+    let index = first.first_difference(&hovered).unwrap();
+    let background = |list: &DisplayList| match &list.commands()[index] {
+        DrawCommand::FillRect { rect, .. } => *rect,
+        command => panic!("unexpected command {:?}", command),
+    };
+    assert_eq!(background(&first), background(&hovered));
+    assert_eq!(background(&first).y, 38.0);
+}
+
+#[test]
+fn first_difference_of_prefix_is_its_length() {
+    let short = list(vec![fill(0.0), fill(10.0)]);
+    let long = list(vec![fill(0.0), fill(10.0), fill(20.0)]);
+    assert_eq!(short.first_difference(&long), Some(2));
+    assert_eq!(long.first_difference(&short), Some(2));
+    assert_eq!(long.first_difference(&list(vec![fill(0.0), fill(15.0)])), Some(1));
+    assert_eq!(DisplayList::new().first_difference(&DisplayList::new()), None);
+}
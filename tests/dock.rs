@@ -0,0 +1,114 @@
+use glui::core::{Orientation, Rect};
+use glui::widgets::{DockLayout, DockNode, DockPosition, FloatingPanel};
+
+fn layout() -> DockLayout {
+    DockLayout::new(DockNode::split(
+        Orientation::Horizontal,
+        0.3,
+        DockNode::tabs(vec!["Files", "Search"]),
+        DockNode::tabs(vec!["Editor"]),
+    ))
+}
+
+#[test]
+fn dock_splits_target_tabs() {
+    let mut layout = layout();
+    assert!(layout.dock("Search", "Editor", DockPosition::Bottom));
+
+    let expected = DockNode::split(
+        Orientation::Horizontal,
+        0.3,
+        DockNode::tabs(vec!["Files"]),
+        DockNode::split(Orientation::Vertical, 0.5, DockNode::tabs(vec!["Editor"]), DockNode::tabs(vec!["Search"])),
+    );
+    assert_eq!(layout.root, Some(expected));
+}
+
+#[test]
+fn dock_center_adds_selected_tab() {
+    let mut layout = layout();
+    assert!(layout.dock("Files", "Editor", DockPosition::Center));
+
+    let expected = DockNode::split(
+        Orientation::Horizontal,
+        0.3,
+        DockNode::tabs(vec!["Search"]),
+        DockNode::Tabs { panels: vec!["Editor".to_owned(), "Files".to_owned()], selected: 1 },
+    );
+    assert_eq!(layout.root, Some(expected));
+}
+
+#[test]
+fn dock_rejects_missing_target() {
+    let mut layout = layout();
+    assert!(! layout.dock("Files", "Console", DockPosition::Left));
+    assert!(! layout.dock("Files", "Files", DockPosition::Left));
+    assert_eq!(layout, self::layout());
+}
+
+#[test]
+fn dock_collapses_emptied_split() {
+    // Moving the only panel of one side of a split leaves just the other side
+    let mut layout = layout();
+    assert!(layout.dock("Editor", "Files", DockPosition::Right));
+
+    let expected = DockNode::split(
+        Orientation::Horizontal,
+        0.5,
+        DockNode::Tabs { panels: vec!["Files".to_owned(), "Search".to_owned()], selected: 0 },
+        DockNode::tabs(vec!["Editor"]),
+    );
+    assert_eq!(layout.root, Some(expected));
+}
+
+#[test]
+fn undock_moves_panel_to_floating() {
+    let mut layout = layout();
+    let region = Rect { x: 10.0, y: 20.0, w: 200.0, h: 100.0 };
+    layout.undock("Editor", region);
+
+    assert_eq!(layout.root, Some(DockNode::tabs(vec!["Files", "Search"])));
+    assert_eq!(layout.floating, vec![FloatingPanel { panel: "Editor".to_owned(), region }]);
+    assert!(layout.contains("Editor"));
+
+    // Docking a floating panel takes it out of its window again
+    assert!(layout.dock("Editor", "Files", DockPosition::Left));
+    assert!(layout.floating.is_empty());
+}
+
+#[test]
+fn remove_collapses_empty_splits() {
+    let mut layout = layout();
+    assert!(layout.remove("Editor"));
+    assert_eq!(layout.root, Some(DockNode::tabs(vec!["Files", "Search"])));
+
+    assert!(layout.remove("Files"));
+    assert_eq!(layout.root, Some(DockNode::tabs(vec!["Search"])));
+
+    assert!(layout.remove("Search"));
+    assert_eq!(layout.root, None);
+    assert!(! layout.remove("Search"));
+}
+
+#[test]
+fn remove_keeps_selected_tab() {
+    let mut layout = DockLayout::new(DockNode::Tabs {
+        panels: vec!["Files".to_owned(), "Search".to_owned(), "Editor".to_owned()],
+        selected: 2,
+    });
+    assert!(layout.remove("Files"));
+    assert_eq!(layout.root, Some(DockNode::Tabs { panels: vec!["Search".to_owned(), "Editor".to_owned()], selected: 1 }));
+
+    assert!(layout.remove("Editor"));
+    assert_eq!(layout.root, Some(DockNode::Tabs { panels: vec!["Search".to_owned()], selected: 0 }));
+}
+
+#[test]
+fn json_round_trip() {
+    let mut layout = layout();
+    layout.dock("Search", "Editor", DockPosition::Top);
+    layout.undock("Files", Rect { x: 5.0, y: 5.0, w: 150.0, h: 90.0 });
+
+    let json = layout.to_json().unwrap();
+    assert_eq!(DockLayout::from_json(&json).unwrap(), layout);
+}
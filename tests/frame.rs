@@ -0,0 +1,142 @@
+use glui::core::{GluiFrame, Id, Layout, Rect, Renderer, Sizing, MAX_LAYOUT_PASSES};
+use glui::testing::Harness;
+use glui::widgets::{Button, Label};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(400, 300);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// A container that is as large as its content.
+fn shrink_to_fit() -> Layout {
+    Layout::column().padding(5.0).width(Sizing::Auto).height(Sizing::Auto)
+}
+
+/// A panel fitting a label whose text can be changed from the outside, and
+/// below it a panel fitting a button whose label grows with each click.
+struct Growing {
+    clicks: usize,
+    text: String,
+    /// The number of layout passes run so far.
+    passes: usize,
+}
+
+impl Growing {
+    fn ui<R: Renderer>(&mut self, frame: &mut GluiFrame<R>) -> Rect {
+        self.passes += 1;
+        frame.layout(Layout::column().height(Sizing::Auto), |frame| {
+            frame.layout(shrink_to_fit(), |frame| Label::new(&self.text).reify(frame));
+        });
+        frame.layout(shrink_to_fit(), |frame| {
+            let label = format!("Clicked {} times", "very ".repeat(self.clicks));
+            let button = Button::with_label(label).reify(Id::from_key("button"), frame);
+            if button.clicked() {
+                self.clicks += 1;
+                frame.invalidate();
+            }
+            button.rect
+        })
+    }
+}
+
+#[test]
+fn click_is_delivered_once_despite_relayout() {
+    let mut harness = harness();
+    let mut growing = Growing { clicks: 0, text: "Short".to_owned(), passes: 0 };
+    harness.frame(|frame| growing.ui(frame));
+    let button = harness.frame(|frame| growing.ui(frame)).value;
+
+    // The label changes together with the click, so the frame is laid out
+    // again before the input is delivered
+    harness.mouse_move(button.x + 5.0, button.y + 5.0);
+    harness.mouse_down();
+    harness.frame(|frame| growing.ui(frame));
+    growing.text = "A much longer label".to_owned();
+    growing.passes = 0;
+    harness.mouse_up();
+    let output = harness.frame(|frame| growing.ui(frame));
+    assert!(growing.passes > 1);
+    assert_eq!(growing.clicks, 1);
+
+    // The click changed the size of the button, which is shown in the next frame
+    assert!(output.redraw);
+    assert_eq!(output.value.w, button.w);
+    let output = harness.frame(|frame| growing.ui(frame));
+    assert_eq!(growing.clicks, 1);
+    assert!(output.value.w > button.w);
+    assert!(! output.redraw);
+}
+
+#[test]
+fn shrink_to_fit_has_final_size_in_first_frame() {
+    let mut harness = harness();
+    let output = harness.frame(|frame| {
+        let region = frame.begin_layout(shrink_to_fit());
+        Button::with_label("Fixed").size(150.0, 24.0).reify(Id::from_key("button"), frame);
+        frame.end_layout();
+        region
+    });
+    assert_eq!(output.value, Rect { x: 0.0, y: 0.0, w: 160.0, h: 34.0 });
+    assert!(! output.redraw);
+}
+
+#[test]
+fn layout_passes_are_capped() {
+    let mut harness = harness();
+    let mut growing = Growing { clicks: 0, text: String::new(), passes: 0 };
+
+    // The label grows in every pass, such that the layout never settles
+    let output = harness.frame(|frame| {
+        growing.text.push('x');
+        growing.ui(frame)
+    });
+    assert_eq!(growing.passes, MAX_LAYOUT_PASSES);
+    assert!(output.redraw);
+}
+
+#[derive(Default)]
+struct Counter(u32);
+
+#[derive(Default)]
+struct Name(String);
+
+#[test]
+fn with_state_keeps_state_per_type() {
+    let mut harness = harness();
+    let id = Id::from_key("widget");
+    let mut ui = |frame: &mut GluiFrame<_>| {
+        let count = frame.with_state(id, |_, counter: &mut Counter| {
+            counter.0 += 1;
+            counter.0
+        });
+        let name = frame.with_state(id, |_, name: &mut Name| {
+            name.0.push('a');
+            name.0.clone()
+        });
+        (count, name)
+    };
+    harness.frame(&mut ui);
+    let output = harness.frame(&mut ui);
+    assert_eq!(output.value, (2, "aa".to_owned()));
+}
+
+#[test]
+fn state_is_dropped_when_widget_is_not_shown() {
+    let mut harness = harness();
+    harness.glui_mut().state_lifetime = 3;
+    let id = Id::from_key("widget");
+    harness.frame(|frame| frame.set_state(id, Counter(5)));
+
+    // Using the state keeps it, while frames without the widget drop it eventually
+    for _ in 0 .. 5 {
+        harness.frame(|frame| assert_eq!(frame.state::<Counter>(id).map(|counter| counter.0), Some(5)));
+    }
+    for _ in 0 .. 3 {
+        assert!(harness.glui().state::<Counter>(id).is_some());
+        harness.frame(|_| ());
+    }
+    assert!(harness.glui().state::<Counter>(id).is_none());
+}
@@ -0,0 +1,67 @@
+use glui::core::{Grid, GluiFrame, Id, Rect, Renderer, Track};
+use glui::testing::Harness;
+use glui::widgets::{form_field, Form, Label, TextBox, TextBoxState};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(400, 300);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// The regions of a short and a long label in an automatic column, the
+/// widgets next to them, and the width of the long label text.
+fn labels<R: Renderer>(frame: &mut GluiFrame<R>) -> (Vec<Rect>, f64) {
+    let text_width = frame.text_measure("A much longer label").w;
+    let grid = Grid::new(vec![Track::Auto, Track::Fill]).spacing(8.0, 4.0).padding(10.0);
+    let rects = frame.grid(Id::from_key("grid"), &grid, |frame| {
+        vec![
+            Label::new("Name").reify(frame).rect,
+            Label::new("Next to name").reify(frame).rect,
+            Label::new("A much longer label").reify(frame).rect,
+            Label::new("Next to long label").reify(frame).rect,
+        ]
+    });
+    (rects, text_width)
+}
+
+#[test]
+fn auto_column_fits_widest_label() {
+    let mut harness = harness();
+    let output = harness.frame(labels);
+    let (rects, text_width) = output.value;
+    assert!(text_width > 0.0);
+
+    // The measurement is applied within the same frame
+    assert_eq!(rects[0].x, 10.0);
+    assert_eq!(rects[0].w, text_width);
+    assert_eq!(rects[2].w, text_width);
+    assert_eq!(rects[1].x, 10.0 + text_width + 8.0);
+    assert_eq!(rects[3].x, rects[1].x);
+    assert_eq!(rects[1].w, 400.0 - 10.0 - rects[1].x);
+    assert!(! output.redraw);
+}
+
+#[test]
+fn form_aligns_fields() {
+    let mut harness = harness();
+    let mut first = TextBoxState::new();
+    let mut second = TextBoxState::new();
+    let output = harness.frame(|frame| {
+        let label_width = frame.text_measure("E-Mail address").w;
+        let fields = Form::new().reify(Id::from_key("form"), frame, |frame| {
+            let first = form_field(frame, "Name", |frame| TextBox::new(&mut first).reify(Id::from_key("first"), frame));
+            let second = form_field(frame, "E-Mail address", |frame| TextBox::new(&mut second).reify(Id::from_key("second"), frame));
+            (first.rect, second.rect)
+        });
+        (fields, label_width)
+    });
+    let ((first, second), label_width) = output.value;
+    // Both fields start after the column of the widest label
+    assert!(first.x > label_width);
+    assert_eq!(first.x, second.x);
+    assert_eq!(first.w, second.w);
+    assert_eq!(first.x + first.w, 400.0);
+    assert!(second.y > first.y + first.h);
+}
@@ -0,0 +1,185 @@
+use glui::core::{ButtonState, GluiFrame, Id, InputEvent, Key, Layout, Modifiers, Point, PointerButton, Renderer};
+use glui::recording::{RecordedEvent, Recording};
+use glui::testing::Harness;
+use glui::widgets::{Button, TextBox, TextBoxState};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+/// The counter of the demo, with the buttons at (10, 10), (10, 38) and (10, 66)
+/// and the text box at (10, 94), each 150 by 24 units large.
+struct Counter {
+    counter: i32,
+    email: TextBoxState,
+}
+
+/// The IDs of the widgets of the counter as seen by the `GluiState`.
+struct CounterIds {
+    increment: Id,
+    decrement: Id,
+    email: Id,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Counter { counter: 0, email: TextBoxState::new() }
+    }
+
+    fn ui<R: Renderer>(&mut self, frame: &mut GluiFrame<R>) -> CounterIds {
+        let increment_id = Id::from_key("increment");
+        let decrement_id = Id::from_key("decrement");
+        let reset_id = Id::from_key("reset");
+        let email_id = Id::from_key("email");
+
+        frame.layout(Layout::column().padding(10.).spacing(4.), |frame| {
+            let label = format!("Already clicked {} times", self.counter);
+            if Button::with_label(&label).size(150., 24.).reify(increment_id, frame).clicked() {
+                self.counter += 1;
+                frame.invalidate();
+            }
+            if Button::with_label("Decrement").size(150., 24.).reify(decrement_id, frame).clicked() {
+                self.counter -= 1;
+                frame.invalidate();
+            }
+            if Button::with_label("Reset").size(150., 24.).reify(reset_id, frame).clicked() {
+                self.counter = 0;
+                frame.invalidate();
+            }
+            TextBox::new(&mut self.email)
+                .hint("E-Mail address")
+                .size(150., 24.)
+                .reify(email_id, frame);
+
+            CounterIds {
+                increment: frame.scoped_id(increment_id),
+                decrement: frame.scoped_id(decrement_id),
+                email: frame.scoped_id(email_id),
+            }
+        })
+    }
+}
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+#[test]
+fn clicking_decrement_twice() {
+    let mut harness = harness();
+    let mut counter = Counter::new();
+    harness.frame(|frame| counter.ui(frame));
+
+    harness.click(50.0, 50.0, |frame| counter.ui(frame));
+    harness.click(50.0, 50.0, |frame| counter.ui(frame));
+    assert_eq!(counter.counter, -2);
+}
+
+#[test]
+fn hover_active_and_focus() {
+    let mut harness = harness();
+    let mut counter = Counter::new();
+    let ids = harness.frame(|frame| counter.ui(frame)).value;
+
+    harness.mouse_move(50.0, 50.0);
+    harness.frame(|frame| counter.ui(frame));
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.hover, Some(ids.decrement));
+    assert_eq!(output.active, None);
+
+    harness.mouse_down();
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.active, Some(ids.decrement));
+
+    // The button stays active while the mouse is dragged away, but is no longer hovered
+    harness.mouse_move(250.0, 200.0);
+    harness.frame(|frame| counter.ui(frame));
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.hover, None);
+    assert_eq!(output.active, Some(ids.decrement));
+
+    // Releasing the mouse elsewhere neither clicks nor moves the focus from the first button
+    harness.mouse_up();
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.active, None);
+    assert_eq!(output.focus, Some(ids.increment));
+    assert_eq!(counter.counter, 0);
+
+    let output = harness.click(50.0, 50.0, |frame| counter.ui(frame));
+    assert_eq!(output.focus, Some(ids.decrement));
+}
+
+#[test]
+fn keyboard_moves_focus_and_clicks() {
+    let mut harness = harness();
+    let mut counter = Counter::new();
+
+    // The first focusable widget receives the focus
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.focus, Some(output.value.increment));
+
+    harness.key_press(Key::Tab, Modifiers::NONE);
+    harness.frame(|frame| counter.ui(frame));
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.focus, Some(output.value.decrement));
+
+    harness.key_press(Key::Enter, Modifiers::NONE);
+    harness.frame(|frame| counter.ui(frame));
+    assert_eq!(counter.counter, -1);
+
+    harness.key_press(Key::Tab, Modifiers::SHIFT);
+    harness.frame(|frame| counter.ui(frame));
+    let output = harness.frame(|frame| counter.ui(frame));
+    assert_eq!(output.focus, Some(output.value.increment));
+}
+
+#[test]
+fn typing_into_text_box() {
+    let mut harness = harness();
+    let mut counter = Counter::new();
+    harness.frame(|frame| counter.ui(frame));
+
+    let output = harness.click(50.0, 106.0, |frame| counter.ui(frame));
+    assert_eq!(output.focus, Some(output.value.email));
+
+    harness.type_text("a@exmple.org");
+    harness.frame(|frame| counter.ui(frame));
+    assert_eq!(counter.email.to_string(), "a@exmple.org");
+
+    // Move the cursor behind "ex" and insert the missing letter
+    for _ in 0 .. "mple.org".len() {
+        harness.key_press(Key::Left, Modifiers::NONE);
+    }
+    harness.type_text("a");
+    harness.frame(|frame| counter.ui(frame));
+    assert_eq!(counter.email.to_string(), "a@example.org");
+
+    // Backspace arrives as text input
+    harness.type_text("\u{8}");
+    harness.frame(|frame| counter.ui(frame));
+    assert_eq!(counter.email.to_string(), "a@exmple.org");
+}
+
+#[test]
+fn replay_recorded_clicks() {
+    let event = |frame, event| RecordedEvent { frame, time_ms: frame * 16, event };
+    let recording = Recording {
+        frames: 6,
+        events: vec![
+            event(0, InputEvent::MouseMove(Point { x: 50.0, y: 50.0 })),
+            event(1, InputEvent::PointerButton(PointerButton::Left, ButtonState::Pressed)),
+            event(2, InputEvent::PointerButton(PointerButton::Left, ButtonState::Released)),
+            event(3, InputEvent::MouseMove(Point { x: 50.0, y: 20.0 })),
+            event(4, InputEvent::PointerButton(PointerButton::Left, ButtonState::Pressed)),
+            event(5, InputEvent::PointerButton(PointerButton::Left, ButtonState::Released)),
+        ],
+    };
+
+    let mut harness = harness();
+    let mut counter = Counter::new();
+    let outputs = harness.replay(recording, |frame| counter.ui(frame));
+    assert_eq!(outputs.len(), 6);
+    assert_eq!(outputs[1].active, Some(outputs[1].value.decrement));
+    assert_eq!(outputs[4].active, Some(outputs[4].value.increment));
+    assert_eq!(counter.counter, 0);
+}
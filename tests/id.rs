@@ -0,0 +1,79 @@
+use glui::core::{GluiFrame, Id, Renderer};
+use glui::mk_id;
+use glui::testing::Harness;
+use glui::widgets::Button;
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// A button with an ID generated in this function, and the line it was generated at.
+fn remove_button<R: Renderer>(frame: &mut GluiFrame<R>) -> (Id, u32) {
+    let (id, line) = (mk_id!(), line!());
+    Button::with_label("Remove").reify(id, frame);
+    (frame.scoped_id(id), line)
+}
+
+#[test]
+fn loops_get_distinct_ids() {
+    let mut harness = harness();
+    let output = harness.frame(|frame| {
+        let mut ids = Vec::new();
+        for index in 0 .. 3 {
+            ids.push(frame.with_id(&index, |frame| remove_button(frame).0));
+        }
+        for index in 0 .. 3 {
+            frame.push_id("second loop");
+            ids.push(frame.with_id(&index, |frame| remove_button(frame).0));
+            frame.pop_id();
+        }
+        for index in 0 .. 3 {
+            let id = mk_id!(index);
+            Button::with_label("Keyed").reify(id, frame);
+            ids.push(frame.scoped_id(id));
+        }
+        ids
+    });
+
+    assert!(output.duplicate_ids.is_empty());
+    let ids = output.value;
+    for (index, id) in ids.iter().enumerate() {
+        assert!(! ids[index + 1 ..].contains(id), "ID {} is used twice", index);
+    }
+}
+
+/// Two remove buttons, either in separate ID scopes or in the same one.
+/// Returns the line the ID of the second button was generated at.
+fn remove_buttons<R: Renderer>(frame: &mut GluiFrame<R>, scoped: bool) -> u32 {
+    if scoped {
+        frame.with_id("first", remove_button);
+        frame.with_id("second", remove_button).1
+    } else {
+        remove_button(frame);
+        remove_button(frame).1
+    }
+}
+
+#[test]
+#[cfg_attr(not(debug_assertions), ignore)]
+fn duplicates_are_reported_with_source() {
+    let mut harness = harness();
+
+    // Calling the helper twice in the same scope generates the same ID
+    let output = harness.frame(|frame| remove_buttons(frame, false));
+    assert_eq!(output.duplicate_ids.len(), 1);
+    let duplicate = &output.duplicate_ids[0];
+    let source = duplicate.id.source().unwrap();
+    assert_eq!((source.file, source.line), (file!(), output.value));
+    assert_eq!(duplicate.regions.len(), 2);
+
+    // Duplicates are reported in every frame in which they occur
+    let output = harness.frame(|frame| remove_buttons(frame, false));
+    assert_eq!(output.duplicate_ids.len(), 1);
+    let output = harness.frame(|frame| remove_buttons(frame, true));
+    assert!(output.duplicate_ids.is_empty());
+}
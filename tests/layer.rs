@@ -0,0 +1,148 @@
+use glui::core::{DisplayList, DrawCommand, GluiFrame, Id, Layer, Layout, Rect, Renderer};
+use glui::testing::Harness;
+use glui::widgets::{Button, Label};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+const BUTTON: Rect = Rect { x: 10.0, y: 10.0, w: 150.0, h: 24.0 };
+
+/// The texts drawn in the frame, in drawing order.
+fn texts(list: &DisplayList) -> Vec<&str> {
+    list.commands().iter()
+        .filter_map(|command| match command {
+            DrawCommand::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn layers_are_drawn_bottom_to_top() {
+    let mut harness = harness();
+    let output = harness.frame(|frame| {
+        frame.with_layer(Layer::Tooltip, |frame| { Label::new("tooltip").reify(frame); });
+        Label::new("normal").reify(frame);
+        frame.with_layer(Layer::Modal, |frame| { Label::new("modal").reify(frame); });
+        frame.with_layer(Layer::Background, |frame| { Label::new("background").reify(frame); });
+        frame.with_layer(Layer::Popup, |frame| { Label::new("first popup").reify(frame); });
+        Label::new("later normal").reify(frame);
+        frame.with_layer(Layer::Popup, |frame| { Label::new("second popup").reify(frame); });
+    });
+    // Within a layer, the creation order is kept
+    assert_eq!(texts(&output.display_list), vec!["background", "normal", "later normal", "first popup", "second popup", "tooltip", "modal"]);
+}
+
+#[test]
+fn popup_takes_mouse_from_normal_layer() {
+    let mut harness = harness();
+    let mut clicked = (false, false);
+    let mut ui = |frame: &mut GluiFrame<_>| {
+        let popup = frame.with_layer(Layer::Popup, |frame| {
+            Button::with_label("Popup").region(BUTTON).reify(Id::from_key("popup"), frame)
+        });
+        let normal = Button::with_label("Normal").region(BUTTON).reify(Id::from_key("normal"), frame);
+        clicked = (clicked.0 || popup.clicked(), clicked.1 || normal.clicked());
+        (frame.scoped_id(Id::from_key("popup")), popup.hovered, normal.hovered)
+    };
+    harness.frame(&mut ui);
+
+    harness.mouse_move(50.0, 20.0);
+    let output = harness.frame(&mut ui);
+    let (popup, popup_hovered, normal_hovered) = output.value;
+    assert_eq!(output.hover, Some(popup));
+    assert!(popup_hovered && ! normal_hovered);
+
+    harness.click(50.0, 20.0, &mut ui);
+    assert_eq!(clicked, (true, false));
+}
+
+/// A button at (10, 10) and below it a button at (10, 30), with the mouse
+/// blocked between them if `block` is set. Returns which buttons were clicked.
+fn blocked<R: Renderer>(frame: &mut GluiFrame<R>, block: bool) -> (bool, bool) {
+    let below = Button::with_label("Below").region(BUTTON).reify(Id::from_key("below"), frame);
+    if block {
+        frame.block_input(Rect { x: 0.0, y: 0.0, w: 320.0, h: 40.0 });
+    }
+    // Widgets created later in the same layer are above the blocked region
+    let above = Button::with_label("Above").region(Rect { y: 30.0, .. BUTTON }).reify(Id::from_key("above"), frame);
+    (below.clicked(), above.clicked())
+}
+
+#[test]
+fn block_input_hides_widgets_below() {
+    let mut harness = harness();
+    harness.frame(|frame| blocked(frame, true));
+
+    let output = harness.click(50.0, 15.0, |frame| blocked(frame, true));
+    assert_eq!(output.hover, None);
+    assert_eq!(output.value, (false, false));
+    let output = harness.click(50.0, 40.0, |frame| blocked(frame, true));
+    assert_eq!(output.value, (false, true));
+
+    harness.frame(|frame| blocked(frame, false));
+    let output = harness.click(50.0, 15.0, |frame| blocked(frame, false));
+    assert_eq!(output.value, (true, false));
+}
+
+/// A dropdown at (10, 10) whose list opens in the popup layer below it,
+/// covering the button created after the dropdown at (10, 38).
+struct Dropdown {
+    open: bool,
+    selected: Option<usize>,
+    later_clicks: usize,
+}
+
+impl Dropdown {
+    fn ui<R: Renderer>(&mut self, frame: &mut GluiFrame<R>) {
+        frame.layout(Layout::column().padding(10.0).spacing(4.0), |frame| {
+            let button = Button::with_label("Choose").size(150.0, 24.0).reify(Id::from_key("choose"), frame);
+            if button.clicked() {
+                self.open = ! self.open;
+            }
+            if self.open {
+                frame.with_layer(Layer::Popup, |frame| {
+                    for (index, option) in ["Red", "Green", "Blue"].iter().enumerate() {
+                        let region = Rect { y: button.rect.y + 28.0 * (index + 1) as f64, .. button.rect };
+                        if Button::with_label(*option).region(region).reify(Id::from_key(option), frame).clicked() {
+                            self.selected = Some(index);
+                            self.open = false;
+                        }
+                    }
+                });
+            }
+            if Button::with_label("Later").size(150.0, 24.0).reify(Id::from_key("later"), frame).clicked() {
+                self.later_clicks += 1;
+            }
+        });
+    }
+}
+
+#[test]
+fn dropdown_list_covers_later_widgets() {
+    let mut harness = harness();
+    let mut dropdown = Dropdown { open: false, selected: None, later_clicks: 0 };
+    harness.frame(|frame| dropdown.ui(frame));
+
+    harness.click(50.0, 20.0, |frame| dropdown.ui(frame));
+    assert!(dropdown.open);
+
+    // The list is drawn above the later button and receives the click
+    let output = harness.frame(|frame| dropdown.ui(frame));
+    assert_eq!(texts(&output.display_list), vec!["Choose", "Later", "Red", "Green", "Blue"]);
+
+    harness.click(50.0, 50.0, |frame| dropdown.ui(frame));
+    assert_eq!(dropdown.selected, Some(0));
+    assert!(! dropdown.open);
+    assert_eq!(dropdown.later_clicks, 0);
+
+    // Once the list is closed, the later button can be clicked again
+    harness.frame(|frame| dropdown.ui(frame));
+    harness.click(50.0, 50.0, |frame| dropdown.ui(frame));
+    assert_eq!(dropdown.later_clicks, 1);
+}
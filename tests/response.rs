@@ -0,0 +1,136 @@
+use glui::core::{GluiFrame, Id, Key, Layout, Modifiers, Point, PointerButton, Renderer, Response};
+use glui::testing::Harness;
+use glui::widgets::Button;
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// Two buttons at (10, 10) and (10, 38), each 150 by 24 units large.
+fn buttons<R: Renderer>(frame: &mut GluiFrame<R>) -> (Response, Response) {
+    frame.layout(Layout::column().padding(10.0).spacing(4.0), |frame| {
+        let first = Button::with_label("First").size(150.0, 24.0).reify(Id::from_key("first"), frame);
+        let second = Button::with_label("Second").size(150.0, 24.0).reify(Id::from_key("second"), frame);
+        (first, second)
+    })
+}
+
+/// Press and release the mouse button at the given position, returning the
+/// responses of the frame in which it was released.
+fn click_with(harness: &mut Harness, button: PointerButton, x: f64, y: f64) -> (Response, Response) {
+    harness.mouse_move(x, y);
+    harness.button_down(button);
+    harness.frame(buttons);
+    harness.button_up(button);
+    harness.frame(buttons).value
+}
+
+#[test]
+fn secondary_click_opens_context_menu() {
+    let mut harness = harness();
+    harness.frame(buttons);
+
+    let (first, second) = click_with(&mut harness, PointerButton::Right, 50.0, 20.0);
+    assert!(first.secondary_clicked());
+    assert!(first.context_menu);
+    assert!(! first.clicked());
+    assert!(! second.secondary_clicked());
+
+    let (first, _) = click_with(&mut harness, PointerButton::Middle, 50.0, 20.0);
+    assert!(first.clicked_by(PointerButton::Middle));
+    assert!(! first.context_menu);
+}
+
+#[test]
+fn secondary_click_needs_press_on_widget() {
+    let mut harness = harness();
+    harness.frame(buttons);
+
+    // Pressing on one button and releasing on another clicks neither
+    for button in &[PointerButton::Left, PointerButton::Right, PointerButton::Middle] {
+        harness.mouse_move(50.0, 20.0);
+        harness.button_down(*button);
+        harness.frame(buttons);
+        harness.mouse_move(50.0, 50.0);
+        harness.frame(buttons);
+        harness.button_up(*button);
+        let (first, second) = harness.frame(buttons).value;
+        assert!(first.clicks.is_empty());
+        assert!(second.clicks.is_empty());
+        assert!(! second.context_menu);
+    }
+}
+
+#[test]
+fn double_click_within_interval() {
+    let mut harness = harness();
+    harness.frame(buttons);
+
+    let (first, _) = harness.click(50.0, 20.0, buttons).value;
+    assert!(first.clicked());
+    assert!(! first.double_clicked);
+    let (first, _) = harness.click(50.0, 20.0, buttons).value;
+    assert!(first.double_clicked);
+
+    // The third click is no double click, nor are clicks that are too far apart
+    let (first, _) = harness.click(50.0, 20.0, buttons).value;
+    assert!(first.clicked());
+    assert!(! first.double_clicked);
+    harness.advance_time(1.0);
+    harness.click(50.0, 20.0, buttons);
+    harness.advance_time(1.0);
+    let (first, _) = harness.click(50.0, 20.0, buttons).value;
+    assert!(! first.double_clicked);
+}
+
+#[test]
+fn drag_delta_while_pressed() {
+    let mut harness = harness();
+    harness.frame(buttons);
+
+    harness.mouse_move(50.0, 20.0);
+    harness.mouse_down();
+    let (first, _) = harness.frame(buttons).value;
+    assert!(first.pressed);
+    assert_eq!(first.drag_delta, Point::zero());
+
+    harness.mouse_move(60.0, 15.0);
+    let (first, second) = harness.frame(buttons).value;
+    assert_eq!(first.drag_delta, Point { x: 10.0, y: -5.0 });
+    assert!(first.dragged());
+    assert_eq!(second.drag_delta, Point::zero());
+
+    // Without movement there is no delta, and none after releasing the button
+    let (first, _) = harness.frame(buttons).value;
+    assert!(! first.dragged());
+    harness.mouse_up();
+    harness.mouse_move(80.0, 20.0);
+    let (first, _) = harness.frame(buttons).value;
+    assert!(! first.pressed);
+    assert_eq!(first.drag_delta, Point::zero());
+}
+
+#[test]
+fn focus_changes_are_reported_once() {
+    let mut harness = harness();
+
+    // The first button receives the focus in the first frame
+    let (first, second) = harness.frame(buttons).value;
+    assert!(first.has_focus && first.gained_focus);
+    assert!(! second.has_focus);
+    let (first, _) = harness.frame(buttons).value;
+    assert!(first.has_focus && ! first.gained_focus);
+
+    harness.key_press(Key::Tab, Modifiers::NONE);
+    let (first, second) = harness.frame(buttons).value;
+    assert!(first.lost_focus && ! first.has_focus);
+    assert!(second.gained_focus && second.has_focus);
+
+    let (first, second) = harness.frame(buttons).value;
+    assert!(! first.lost_focus);
+    assert!(! second.gained_focus);
+}
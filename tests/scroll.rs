@@ -0,0 +1,177 @@
+use glui::core::{GluiFrame, Id, Key, Layout, Modifiers, Placement, Rect, Renderer, ScrollArea, SizeHint};
+use glui::backend::software::SoftwareRenderer;
+use glui::testing::{FrameOutput, Harness};
+use glui::widgets::Button;
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+fn harness() -> Harness {
+    let mut harness = Harness::new(320, 240);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// A block of content that is 180 units wide and `h` units high.
+fn block<R: Renderer>(frame: &mut GluiFrame<R>, h: f64) -> Rect {
+    frame.place(Placement::default().size(180.0, h), SizeHint::default())
+}
+
+/// Compare positions that were reached by an animation.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+}
+
+/// Run frames until the UI no longer requests another one, e.g. because
+/// a scroll area is animated.
+fn settle<T, F>(harness: &mut Harness, mut ui: F) -> FrameOutput<T> where
+    F: FnMut(&mut GluiFrame<SoftwareRenderer>) -> T
+{
+    for _ in 0 .. 30 {
+        let output = harness.frame(&mut ui);
+        if ! output.redraw {
+            return output;
+        }
+    }
+    panic!("the UI did not settle");
+}
+
+/// A scroll area of 200 by 200 units at the origin containing a scroll area
+/// of 180 by 100 units, with 400 units of content each. Returns the first
+/// block of the outer and the inner content.
+fn nested<R: Renderer>(frame: &mut GluiFrame<R>) -> (Rect, Rect) {
+    frame.scroll_area(Id::from_key("outer"), &ScrollArea::new().size(200.0, 200.0), |frame| {
+        let outer = block(frame, 50.0);
+        let inner = frame.scroll_area(Id::from_key("inner"), &ScrollArea::new().size(180.0, 100.0), |frame| {
+            let inner = block(frame, 200.0);
+            block(frame, 200.0);
+            inner
+        });
+        block(frame, 250.0);
+        (outer, inner)
+    })
+}
+
+#[test]
+fn innermost_area_consumes_wheel() {
+    let mut harness = harness();
+    harness.frame(nested);
+    harness.frame(nested);
+
+    // Above the inner area, only the inner content moves. The content has been
+    // placed when the input arrives, hence the offset is applied in the next frame.
+    harness.mouse_move(50.0, 100.0);
+    harness.scroll(0.0, -30.0);
+    assert!(harness.frame(nested).redraw);
+    let (outer, inner) = harness.frame(nested).value;
+    assert_eq!(outer.y, 0.0);
+    assert_eq!(inner.y, 50.0 - 30.0);
+
+    // Above the outer area only, the outer content moves
+    harness.mouse_move(50.0, 175.0);
+    harness.scroll_lines(0.0, -1.0);
+    harness.frame(nested);
+    let (outer, inner) = harness.frame(nested).value;
+    assert_eq!(outer.y, -20.0);
+    assert_eq!(inner.y, 30.0 - 30.0);
+
+    // The offsets are clamped to the content
+    harness.scroll(0.0, -1000.0);
+    harness.frame(nested);
+    let (outer, _) = harness.frame(nested).value;
+    assert_eq!(outer.y, 200.0 - 400.0);
+    harness.scroll(0.0, 1000.0);
+    harness.frame(nested);
+    let (outer, _) = harness.frame(nested).value;
+    assert_eq!(outer.y, 0.0);
+}
+
+/// A scroll area of 200 by 200 units at the origin with 800 units of content,
+/// whose scrollbar thumb is 50 units long. Returns the first block of content.
+fn long<R: Renderer>(frame: &mut GluiFrame<R>) -> Rect {
+    frame.scroll_area(Id::from_key("long"), &ScrollArea::new().size(200.0, 200.0), |frame| {
+        let first = block(frame, 400.0);
+        block(frame, 400.0);
+        first
+    })
+}
+
+#[test]
+fn dragging_thumb_scrolls_proportionally() {
+    let mut harness = harness();
+    harness.frame(long);
+    harness.frame(long);
+
+    harness.mouse_move(195.0, 25.0);
+    harness.frame(long);
+    harness.mouse_down();
+    harness.frame(long);
+    harness.mouse_move(195.0, 55.0);
+    harness.frame(long);
+    harness.mouse_up();
+
+    // Each unit the thumb moves scrolls 600 / 150 units of content
+    let first = harness.frame(long).value;
+    assert_eq!(first.y, -120.0);
+
+    // The thumb, now at y = 30, stops at the end of the track
+    harness.mouse_move(195.0, 55.0);
+    harness.frame(long);
+    harness.mouse_down();
+    harness.frame(long);
+    harness.mouse_move(195.0, 400.0);
+    harness.frame(long);
+    harness.mouse_up();
+    let first = harness.frame(long).value;
+    assert_eq!(first.y, -600.0);
+}
+
+/// Five buttons in a scroll area of 200 by 100 units, each 40 units high.
+/// Returns the region of each button.
+fn buttons<R: Renderer>(frame: &mut GluiFrame<R>, focus: Option<usize>) -> Vec<Rect> {
+    let area = ScrollArea::new().size(200.0, 100.0).content(Layout::column());
+    frame.scroll_area(Id::from_key("buttons"), &area, |frame| {
+        (0 .. 5usize)
+            .map(|index| {
+                let id = Id::from_key("button").with(&index);
+                if focus == Some(index) {
+                    frame.request_focus(id);
+                }
+                Button::with_label(format!("Button {}", index)).size(180.0, 40.0).reify(id, frame).rect
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn tab_reveals_focused_child() {
+    let mut harness = harness();
+    harness.frame(|frame| buttons(frame, None));
+
+    for _ in 0 .. 3 {
+        harness.key_press(Key::Tab, Modifiers::NONE);
+        harness.frame(|frame| buttons(frame, None));
+    }
+    let output = settle(&mut harness, |frame| buttons(frame, None));
+    let focused = output.value[3];
+    assert_eq!(output.focus, Some(Id::from_key("button").with(&3usize)));
+    assert_close(focused.y, 100.0 - 40.0);
+
+    // Moving back reveals the previous buttons at the top
+    for _ in 0 .. 2 {
+        harness.key_press(Key::Tab, Modifiers::SHIFT);
+        harness.frame(|frame| buttons(frame, None));
+    }
+    let output = settle(&mut harness, |frame| buttons(frame, None));
+    assert_close(output.value[1].y, 0.0);
+}
+
+#[test]
+fn request_focus_reveals_child() {
+    let mut harness = harness();
+    harness.frame(|frame| buttons(frame, None));
+
+    harness.frame(|frame| buttons(frame, Some(4)));
+    let output = settle(&mut harness, |frame| buttons(frame, None));
+    assert_eq!(output.focus, Some(Id::from_key("button").with(&4usize)));
+    assert_close(output.value[4].y, 100.0 - 40.0);
+}
@@ -0,0 +1,55 @@
+use glui::core::{GluiFrame, Id, Layout, Renderer};
+use glui::testing::{Harness, Tolerance};
+use glui::widgets::{Button, ProgressBar};
+
+const FONT_DATA: &[u8] = include_bytes!("../resources/Roboto-Regular.ttf");
+
+/// The path of a golden image, independent of the directory the tests run in.
+/// Run the tests with `GLUI_BLESS=1` to update the images after intended changes.
+fn golden(name: &str) -> String {
+    format!("{}/tests/golden/{}.png", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+fn harness(width: u32, height: u32) -> Harness {
+    let mut harness = Harness::new(width, height);
+    harness.add_font("default", FONT_DATA).unwrap();
+    harness
+}
+
+/// A single button at (10, 10) that is 150 by 24 units large.
+fn button<R: Renderer>(frame: &mut GluiFrame<R>) {
+    frame.layout(Layout::column().padding(10.0), |frame| {
+        Button::with_label("Click me").size(150.0, 24.0).reify(Id::from_key("button"), frame);
+    });
+}
+
+#[test]
+fn button_states() {
+    let mut harness = harness(170, 44);
+    harness.assert_snapshot(golden("button"), Tolerance::default(), button);
+
+    harness.mouse_move(80.0, 20.0);
+    harness.frame(button);
+    harness.assert_snapshot(golden("button_hover"), Tolerance::default(), button);
+
+    harness.mouse_down();
+    harness.assert_snapshot(golden("button_active"), Tolerance::default(), button);
+
+    // Dragged away, the button is still active but no longer hovered
+    harness.mouse_move(165.0, 40.0);
+    harness.frame(button);
+    harness.assert_snapshot(golden("button_active_away"), Tolerance::default(), button);
+}
+
+#[test]
+fn progress_bar_is_clipped_to_progress() {
+    let mut harness = harness(170, 120);
+    harness.assert_snapshot(golden("progress_bar"), Tolerance::default(), |frame| {
+        frame.layout(Layout::column().padding(10.0).spacing(4.0), |frame| {
+            // Values outside of the range fill nothing or the whole bar
+            for progress in &[-20.0, 0.0, 37.0, 100.0] {
+                ProgressBar::new().progress(*progress).size(150.0, 22.0).reify(frame);
+            }
+        });
+    });
+}
@@ -0,0 +1,67 @@
+use glui::core::{GluiFrame, Id, Placement, Rect, Renderer, SizeHint, Sizing, Splitter};
+use glui::testing::Harness;
+
+/// A splitter filling the whole frame, with the divider between the panes at
+/// x = 197 before it is moved.
+fn panes<R: Renderer>(frame: &mut GluiFrame<R>) -> Vec<Rect> {
+    let splitter = Splitter::horizontal(2).min_size(50.0);
+    let pane = Placement { width: Sizing::Fill, height: Sizing::Fill, .. Placement::default() };
+    frame.splitter(Id::from_key("splitter"), &splitter, |frame| {
+        (0 .. 2).map(|_| frame.place(pane, SizeHint::default())).collect()
+    })
+}
+
+/// Compare sizes that were computed from ratios.
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+}
+
+/// Press the left mouse button on the position and move it by the given distance.
+fn drag(harness: &mut Harness, x: f64, y: f64, distance: f64) {
+    harness.mouse_move(x, y);
+    harness.frame(panes);
+    harness.mouse_down();
+    harness.frame(panes);
+    harness.mouse_move(x + distance, y);
+    harness.frame(panes);
+    harness.mouse_up();
+    harness.frame(panes);
+}
+
+#[test]
+fn dragged_ratio_persists() {
+    let mut harness = Harness::new(400, 300);
+    let output = harness.frame(panes);
+    assert_eq!(output.value[0], Rect { x: 0.0, y: 0.0, w: 197.0, h: 300.0 });
+    assert_eq!(output.value[1], Rect { x: 203.0, y: 0.0, w: 197.0, h: 300.0 });
+
+    drag(&mut harness, 200.0, 150.0, 50.0);
+    for _ in 0 .. 3 {
+        let output = harness.frame(panes);
+        assert_close(output.value[0].w, 247.0);
+        assert_close(output.value[1].x, 253.0);
+        assert_close(output.value[1].w, 147.0);
+    }
+
+    // Dragging beyond the smallest size of the second pane stops at it
+    drag(&mut harness, 250.0, 150.0, 200.0);
+    let output = harness.frame(panes);
+    assert_close(output.value[1].w, 50.0);
+}
+
+#[test]
+fn double_click_resets_ratios() {
+    let mut harness = Harness::new(400, 300);
+    harness.frame(panes);
+    drag(&mut harness, 200.0, 150.0, -100.0);
+    harness.advance_time(1.0);
+
+    // A single click on the divider keeps the panes
+    harness.click(100.0, 150.0, panes);
+    let output = harness.frame(panes);
+    assert_close(output.value[0].w, 97.0);
+
+    harness.click(100.0, 150.0, panes);
+    let output = harness.frame(panes);
+    assert_close(output.value[0].w, 197.0);
+}
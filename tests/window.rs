@@ -0,0 +1,167 @@
+use glui::core::{GluiFrame, Id, Layout, Rect, Renderer, Window};
+use glui::testing::Harness;
+use glui::widgets::Button;
+
+/// The outcome of a frame with two overlapping windows and a button below them.
+#[derive(Default, Debug)]
+struct Clicked {
+    background: bool,
+    first: bool,
+    second: bool,
+}
+
+struct Windows {
+    first_open: bool,
+    second_open: bool,
+}
+
+impl Windows {
+    fn new() -> Self {
+        Windows { first_open: true, second_open: true }
+    }
+}
+
+fn ui<R: Renderer>(windows: &mut Windows, frame: &mut GluiFrame<R>) -> Clicked {
+    let mut clicked = Clicked::default();
+    frame.layout(Layout::column().padding(10.0), |frame| {
+        clicked.background = Button::with_label("Background")
+            .size(150.0, 24.0)
+            .reify(Id::from_key("background"), frame)
+            .clicked();
+    });
+
+    let first = Window::new("First").region(Rect { x: 20.0, y: 20.0, w: 200.0, h: 150.0 });
+    frame.window(Id::from_key("first"), &first, &mut windows.first_open, |frame| {
+        clicked.first = Button::with_label("First")
+            .size(150.0, 24.0)
+            .reify(Id::from_key("first button"), frame)
+            .clicked();
+    });
+
+    let second = Window::new("Second").region(Rect { x: 100.0, y: 100.0, w: 200.0, h: 150.0 });
+    frame.window(Id::from_key("second"), &second, &mut windows.second_open, |frame| {
+        clicked.second = Button::with_label("Second")
+            .size(150.0, 24.0)
+            .reify(Id::from_key("second button"), frame)
+            .clicked();
+    });
+    clicked
+}
+
+#[test]
+fn window_hides_widgets_below() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // The background button spans (10, 10) to (160, 34), partially covered by the first window
+    let output = harness.click(50.0, 50.0, |frame| ui(&mut windows, frame));
+    assert!(! output.value.background);
+
+    let output = harness.click(15.0, 15.0, |frame| ui(&mut windows, frame));
+    assert!(output.value.background);
+}
+
+#[test]
+fn clicking_window_raises_it() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // The button of the second window is on top of the first window
+    let output = harness.click(110.0, 135.0, |frame| ui(&mut windows, frame));
+    assert!(output.value.second);
+
+    // Clicking the visible part of the first window puts it on top
+    harness.click(50.0, 80.0, |frame| ui(&mut windows, frame));
+    let output = harness.click(110.0, 135.0, |frame| ui(&mut windows, frame));
+    assert!(! output.value.second);
+}
+
+#[test]
+fn dragging_title_moves_window() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    harness.mouse_move(60.0, 30.0);
+    harness.mouse_down();
+    harness.frame(|frame| ui(&mut windows, frame));
+    harness.mouse_move(90.0, 50.0);
+    harness.frame(|frame| ui(&mut windows, frame));
+    harness.mouse_up();
+    let output = harness.frame(|frame| {
+        ui(&mut windows, frame);
+        frame.window_rect(Id::from_key("first"))
+    });
+    assert_eq!(output.value, Some(Rect { x: 50.0, y: 40.0, w: 200.0, h: 150.0 }));
+}
+
+#[test]
+fn resizing_border_keeps_min_size() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // Drag the left border of the first window far to the right
+    harness.mouse_move(22.0, 80.0);
+    harness.mouse_down();
+    harness.frame(|frame| ui(&mut windows, frame));
+    harness.mouse_move(300.0, 80.0);
+    harness.frame(|frame| ui(&mut windows, frame));
+    harness.mouse_up();
+    let output = harness.frame(|frame| {
+        ui(&mut windows, frame);
+        frame.window_rect(Id::from_key("first"))
+    });
+    assert_eq!(output.value, Some(Rect { x: 120.0, y: 20.0, w: 100.0, h: 150.0 }));
+}
+
+#[test]
+fn collapsed_window_hides_content() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // The collapse button is on the left of the title bar
+    harness.click(30.0, 30.0, |frame| ui(&mut windows, frame));
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // Widgets see the hit regions of the previous frame, hence the extra frame above.
+    // Only the title bar covers the background now
+    let output = harness.click(110.0, 135.0, |frame| ui(&mut windows, frame));
+    assert!(output.value.second);
+    let output = harness.click(50.0, 80.0, |frame| ui(&mut windows, frame));
+    assert!(! output.value.first);
+}
+
+#[test]
+fn close_button_closes_window() {
+    let mut harness = Harness::new(320, 280);
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // The close button is on the right of the title bar
+    harness.click(205.0, 30.0, |frame| ui(&mut windows, frame));
+    assert!(! windows.first_open);
+    assert!(windows.second_open);
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    // The closed window no longer covers the background
+    let output = harness.click(50.0, 25.0, |frame| ui(&mut windows, frame));
+    assert!(output.value.background);
+}
+
+#[test]
+fn hidden_window_state_is_dropped() {
+    let mut harness = Harness::new(320, 280);
+    harness.glui_mut().state_lifetime = 2;
+    let mut windows = Windows::new();
+    harness.frame(|frame| ui(&mut windows, frame));
+
+    windows.first_open = false;
+    let rect = |frame: &mut GluiFrame<_>| frame.window_rect(Id::from_key("first"));
+    assert!(harness.frame(|frame| { ui(&mut windows, frame); rect(frame) }).value.is_some());
+    assert!(harness.frame(|frame| { ui(&mut windows, frame); rect(frame) }).value.is_some());
+    assert!(harness.frame(|frame| { ui(&mut windows, frame); rect(frame) }).value.is_none());
+}